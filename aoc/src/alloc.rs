@@ -0,0 +1,198 @@
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "alloc-stats")]
+use std::alloc::{GlobalAlloc, Layout, System};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // Const and without destructor, so reading it never allocates
+    static IGNORED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Wraps the system allocator and counts every allocation. Installed as the
+/// global allocator by the `alloc-stats` feature.
+pub struct CountingAllocator;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[cfg_attr(not(feature = "alloc-stats"), allow(dead_code))]
+impl CountingAllocator {
+    fn on_alloc(size: usize) {
+        if is_ignored() {
+            return;
+        }
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn on_dealloc(size: usize) {
+        if is_ignored() {
+            return;
+        }
+        // Memory allocated before the counters existed can be freed too
+        let _ = LIVE_BYTES.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| {
+            Some(live.saturating_sub(size))
+        });
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::on_dealloc(layout.size());
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::on_dealloc(layout.size());
+            Self::on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub allocated_bytes: usize,
+    /// The most memory allocated at once, not counting what was live before.
+    pub peak_live_bytes: usize,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.allocated_bytes),
+            format_bytes(self.peak_live_bytes)
+        )
+    }
+}
+
+pub fn is_enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Stops counting the allocations of the current thread, for threads running
+/// next to the measured code without being part of it, like a progress bar.
+pub fn ignore_current_thread() {
+    IGNORED.with(|ignored| ignored.set(true));
+}
+
+fn is_ignored() -> bool {
+    IGNORED.try_with(Cell::get).unwrap_or(false)
+}
+
+/// Runs `f` and counts its allocations. Returns no stats unless the counting
+/// allocator is installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    let (result, stats) = count_allocations(f);
+    (result, is_enabled().then_some(stats))
+}
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(live_bytes, Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes,
+        peak_live_bytes: PEAK_LIVE_BYTES
+            .load(Ordering::Relaxed)
+            .saturating_sub(live_bytes),
+    };
+    (result, stats)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{value:.1} {}", UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        let (_, stats) = count_allocations(|| {
+            CountingAllocator::on_alloc(4096);
+            CountingAllocator::on_alloc(1024);
+            CountingAllocator::on_dealloc(4096);
+            CountingAllocator::on_dealloc(1024);
+            CountingAllocator::on_alloc(2048);
+            CountingAllocator::on_dealloc(2048);
+        });
+        assert!(stats.allocations >= 3);
+        assert!(stats.allocated_bytes >= 7168);
+        assert!(stats.peak_live_bytes >= 5120);
+
+        let (_, stats) = measure(|| ());
+        assert_eq!(stats.is_some(), is_enabled());
+    }
+
+    #[test]
+    fn test_ignored_thread() {
+        let (_, stats) = count_allocations(|| {
+            std::thread::spawn(|| {
+                ignore_current_thread();
+                CountingAllocator::on_alloc(1 << 40);
+                CountingAllocator::on_dealloc(1 << 40);
+            })
+            .join()
+            .unwrap();
+        });
+        assert!(stats.allocated_bytes < 1 << 40);
+        assert!(stats.peak_live_bytes < 1 << 40);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
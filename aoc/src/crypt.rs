@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{env, fs, io};
+
+use age::secrecy::ExposeSecret;
+use age::x25519::Identity;
+
+pub const ENCRYPTED_EXTENSION: &str = "age";
+pub const KEY_ENV: &str = "AOC_KEY";
+pub const KEY_FILE_ENV: &str = "AOC_KEY_FILE";
+pub const DEFAULT_KEY_FILE: &str = ".aoc-key";
+
+/// Loads the key from `AOC_KEY`, or from the file named by `AOC_KEY_FILE`
+/// (`.aoc-key` by default).
+pub fn load_key() -> io::Result<Identity> {
+    if let Ok(key) = env::var(KEY_ENV) {
+        return parse_key(&key);
+    }
+
+    let path = key_file_path();
+    match fs::read_to_string(&path) {
+        Ok(contents) => parse_key(&contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no key found: set {KEY_ENV} or create {}", path.display()),
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn key_file_path() -> PathBuf {
+    env::var_os(KEY_FILE_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_KEY_FILE))
+}
+
+/// Generates a new key and returns it in the `age-keygen` file format.
+pub fn generate_key() -> String {
+    let identity = Identity::generate();
+    let key = identity.to_string();
+    format!(
+        "# public key: {}\n{}\n",
+        identity.to_public(),
+        key.expose_secret()
+    )
+}
+
+fn parse_key(s: &str) -> io::Result<Identity> {
+    let line = s
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "key is empty"))?;
+    Identity::from_str(line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn encrypt(key: &Identity, plaintext: &[u8]) -> io::Result<Vec<u8>> {
+    age::encrypt(&key.to_public(), plaintext).map_err(io::Error::other)
+}
+
+pub fn decrypt(key: &Identity, ciphertext: &[u8]) -> io::Result<Vec<u8>> {
+    age::decrypt(key, ciphertext).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn is_encrypted(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == ENCRYPTED_EXTENSION)
+}
+
+/// `data/day12.txt` -> `data/day12.txt.age`
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(".");
+    s.push(ENCRYPTED_EXTENSION);
+    PathBuf::from(s)
+}
+
+/// `data/day12.txt.age` -> `data/day12.txt`
+pub fn decrypted_path(path: &Path) -> PathBuf {
+    path.with_extension("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let key = parse_key(&generate_key()).unwrap();
+        let ciphertext = encrypt(&key, b"1000\n2000\n").unwrap();
+        assert_ne!(ciphertext, b"1000\n2000\n");
+        assert_eq!(decrypt(&key, &ciphertext).unwrap(), b"1000\n2000\n");
+
+        let other = Identity::generate();
+        assert!(decrypt(&other, &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_paths() {
+        let path = Path::new("data/day12.txt");
+        assert_eq!(encrypted_path(path), Path::new("data/day12.txt.age"));
+        assert!(is_encrypted(&encrypted_path(path)));
+        assert_eq!(decrypted_path(&encrypted_path(path)), path);
+    }
+}
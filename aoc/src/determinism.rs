@@ -0,0 +1,68 @@
+use std::thread;
+
+use crate::difftest::{outcome, Outcome};
+use crate::solution::Part;
+
+/// Runs a part `runs` times on the input and returns each distinct outcome
+/// with how many runs had it, in the order they first came up.
+///
+/// Every `HashMap` and `HashSet` gets new hasher keys, and each thread starts
+/// from its own random ones, so the first run is on the current thread and
+/// the others on threads of their own, all at the same time.
+pub fn distinct_outcomes(part: Part, input: &str, runs: usize) -> Vec<(Outcome, usize)> {
+    if runs == 0 {
+        return vec![];
+    }
+    let mut outcomes = vec![outcome(part, input)];
+    thread::scope(|scope| {
+        let handles: Vec<_> = (1..runs)
+            .map(|_| scope.spawn(|| outcome(part, input)))
+            .collect();
+        outcomes.extend(
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("panics are caught")),
+        );
+    });
+
+    let mut distinct: Vec<(Outcome, usize)> = Vec::new();
+    for each in outcomes {
+        match distinct.iter_mut().find(|(o, _)| *o == each) {
+            Some((_, count)) => *count += 1,
+            None => distinct.push((each, 1)),
+        }
+    }
+    distinct
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use anyhow::Result;
+
+    use super::distinct_outcomes;
+    use crate::difftest::Outcome;
+
+    fn count(input: &str) -> Result<String> {
+        Ok(input.len().to_string())
+    }
+
+    fn unordered(_: &str) -> Result<String> {
+        let set: HashSet<u32> = (0..100).collect();
+        Ok(format!("{set:?}"))
+    }
+
+    #[test]
+    fn test_distinct_outcomes() {
+        assert_eq!(
+            distinct_outcomes(count, "abc", 4),
+            [(Outcome::Answer("3".to_string()), 4)]
+        );
+        assert!(distinct_outcomes(count, "abc", 0).is_empty());
+
+        let outcomes = distinct_outcomes(unordered, "", 4);
+        assert!(outcomes.len() > 1);
+        assert_eq!(outcomes.iter().map(|&(_, n)| n).sum::<usize>(), 4);
+    }
+}
@@ -0,0 +1,139 @@
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::random::Rng;
+use crate::solution::{Part, Solution};
+
+/// What a part did with an input. Errors and panics are outcomes too, so a
+/// part that panics where its reference returns an error disagrees with it.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Answer(String),
+    Error(String),
+    Panic(String),
+}
+
+impl Outcome {
+    /// Answers must be equal, errors and panics only of the same kind.
+    pub fn agrees_with(&self, other: &Outcome) -> bool {
+        match (self, other) {
+            (Outcome::Answer(a), Outcome::Answer(b)) => a == b,
+            (Outcome::Error(_), Outcome::Error(_)) | (Outcome::Panic(_), Outcome::Panic(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{answer:?}"),
+            Outcome::Error(e) => write!(f, "error: {e}"),
+            Outcome::Panic(message) => write!(f, "panic: {message}"),
+        }
+    }
+}
+
+pub fn outcome(part: Part, input: &str) -> Outcome {
+    match panic::catch_unwind(AssertUnwindSafe(|| part(input))) {
+        Ok(Ok(answer)) => Outcome::Answer(answer),
+        Ok(Err(e)) => Outcome::Error(format!("{e:#}")),
+        Err(payload) => Outcome::Panic(panic_message(payload)),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// An input a part and its reference disagree on.
+pub struct Mismatch {
+    pub seed: u64,
+    pub input: String,
+    pub answer: Outcome,
+    pub reference: Outcome,
+}
+
+/// Runs a part and its reference on inputs of the given size generated from
+/// each seed, and returns the first input they disagree on.
+pub fn find_mismatch(
+    solution: &Solution,
+    part: u8,
+    size: usize,
+    seeds: Range<u64>,
+) -> Option<Mismatch> {
+    let generator = solution.generator?;
+    let (fast, reference) = (solution.part(part)?, solution.reference(part)?);
+    seeds.into_iter().find_map(|seed| {
+        let input = generator(size, &mut Rng::new(seed));
+        let (answer, expected) = (outcome(fast, &input), outcome(reference, &input));
+        (!answer.agrees_with(&expected)).then_some(Mismatch {
+            seed,
+            input,
+            answer,
+            reference: expected,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{bail, Result};
+
+    use super::{find_mismatch, outcome, Outcome};
+    use crate::random::Rng;
+    use crate::solution::Solution;
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size).map(|_| rng.below(10).to_string()).collect()
+    }
+
+    fn count(input: &str) -> Result<String> {
+        Ok(input.len().to_string())
+    }
+
+    fn count_without_nines(input: &str) -> Result<String> {
+        Ok(input.chars().filter(|&c| c != '9').count().to_string())
+    }
+
+    fn fail(_: &str) -> Result<String> {
+        bail!("no answer")
+    }
+
+    fn explode(_: &str) -> Result<String> {
+        panic!("boom")
+    }
+
+    #[test]
+    fn test_outcome() {
+        assert_eq!(outcome(count, "abc"), Outcome::Answer("3".to_string()));
+        assert_eq!(outcome(fail, ""), Outcome::Error("no answer".to_string()));
+        assert_eq!(outcome(explode, ""), Outcome::Panic("boom".to_string()));
+
+        assert!(outcome(fail, "").agrees_with(&Outcome::Error("other".to_string())));
+        assert!(!outcome(fail, "").agrees_with(&outcome(explode, "")));
+        assert!(!outcome(count, "abc").agrees_with(&outcome(count, "ab")));
+    }
+
+    #[test]
+    fn test_find_mismatch() {
+        let solution = Solution::new(2022, 1, &[count, count_without_nines])
+            .with_generator(generate)
+            .with_references(&[Some(count), Some(count)]);
+        assert!(find_mismatch(&solution, 1, 5, 0..100).is_none());
+        assert!(find_mismatch(&solution, 3, 5, 0..100).is_none());
+
+        let mismatch = find_mismatch(&solution, 2, 5, 0..100).expect("some input has a 9");
+        assert!(mismatch.input.contains('9'));
+        assert_eq!(mismatch.reference, Outcome::Answer("5".to_string()));
+        assert_eq!(mismatch.input, generate(5, &mut Rng::new(mismatch.seed)));
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::{crypt, read_puzzle_data};
+
+pub const DATA_DIR: &str = "data";
+pub const INPUT_EXTENSION: &str = "txt";
+pub const ANSWERS_EXTENSION: &str = "answers.json";
+pub const REGRESSIONS_DIR: &str = "regressions";
+
+/// Expected answers of an input, keyed by part number.
+pub type Answers = BTreeMap<u8, String>;
+
+/// A puzzle input, usually stored as `data/<day>/<label>.txt` (or `.txt.age`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+    pub day: u8,
+    pub label: String,
+    pub path: PathBuf,
+}
+
+impl Input {
+    pub fn new(day: u8, label: &str) -> Self {
+        let path = day_dir(day).join(format!("{label}.{INPUT_EXTENSION}"));
+        Self {
+            day,
+            label: label.to_owned(),
+            path,
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        read_puzzle_data(&self.path)
+    }
+
+    /// Creates the `index`-th example input of the day, stored next to the unit test fixtures.
+    pub fn example(year: u16, day: u8, index: usize) -> Self {
+        let (label, file_name) = match index {
+            1 => ("example".to_owned(), format!("day{day}.{INPUT_EXTENSION}")),
+            _ => (
+                format!("example-{index}"),
+                format!("day{day}-{index}.{INPUT_EXTENSION}"),
+            ),
+        };
+        Self {
+            day,
+            label,
+            path: examples_dir(year).join(file_name),
+        }
+    }
+
+    /// Creates a regression example found by `aoc shrink`, stored in the
+    /// `regressions` directory next to the other examples.
+    pub fn regression(year: u16, day: u8, name: &str) -> Self {
+        let file_name = format!("day{day}-{name}.{INPUT_EXTENSION}");
+        Self {
+            day,
+            label: format!("regression-{name}"),
+            path: examples_dir(year).join(REGRESSIONS_DIR).join(file_name),
+        }
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.path.with_extension(ANSWERS_EXTENSION)
+    }
+
+    /// Returns no answers if the answers file does not exist yet.
+    pub fn read_answers(&self) -> io::Result<Answers> {
+        match fs::read_to_string(self.answers_path()) {
+            Ok(s) => serde_json::from_str(&s).map_err(io::Error::from),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn write_answers(&self, answers: &Answers) -> io::Result<()> {
+        let mut json = serde_json::to_string_pretty(answers)?;
+        json.push('\n');
+        fs::write(self.answers_path(), json)
+    }
+}
+
+pub fn day_dir(day: u8) -> PathBuf {
+    Path::new(DATA_DIR).join(day.to_string())
+}
+
+pub fn examples_dir(year: u16) -> PathBuf {
+    PathBuf::from(format!("year{year}/test_data"))
+}
+
+/// Lists all inputs of the day sorted by label, plain text and encrypted alike.
+pub fn day_inputs(day: u8) -> io::Result<Vec<Input>> {
+    let dir = day_dir(day);
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut labels = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let path = match crypt::is_encrypted(&path) {
+            true => crypt::decrypted_path(&path),
+            false => path,
+        };
+        if path.extension().is_some_and(|e| e == INPUT_EXTENSION) {
+            if let Some(label) = path.file_stem().and_then(|s| s.to_str()) {
+                labels.push(label.to_owned());
+            }
+        }
+    }
+    labels.sort();
+    labels.dedup();
+    Ok(labels.iter().map(|l| Input::new(day, l)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_paths() {
+        let input = Input::new(12, "alice");
+        assert_eq!(input.path, Path::new("data/12/alice.txt"));
+        assert_eq!(
+            input.answers_path(),
+            Path::new("data/12/alice.answers.json")
+        );
+
+        let example = Input::example(2022, 13, 1);
+        assert_eq!(example.path, Path::new("year2022/test_data/day13.txt"));
+        assert_eq!(
+            example.answers_path(),
+            Path::new("year2022/test_data/day13.answers.json")
+        );
+        assert_eq!(Input::example(2022, 13, 2).label, "example-2");
+
+        let regression = Input::regression(2022, 7, "cd-root");
+        assert_eq!(
+            regression.path,
+            Path::new("year2022/test_data/regressions/day7-cd-root.txt")
+        );
+        assert_eq!(regression.label, "regression-cd-root");
+    }
+}
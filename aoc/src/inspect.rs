@@ -0,0 +1,239 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// Something in an input that usually comes from a bad copy-paste.
+#[derive(Debug, PartialEq)]
+pub enum Warning {
+    Empty,
+    CrlfEndings { lines: usize },
+    TrailingWhitespace { lines: usize, first: usize },
+    MissingFinalNewline,
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::Empty => write!(f, "the input is empty"),
+            Warning::CrlfEndings { lines } => write!(f, "{lines} lines end with CRLF"),
+            Warning::TrailingWhitespace { lines, first } => {
+                write!(
+                    f,
+                    "{lines} lines end with whitespace, first on line {first}"
+                )
+            }
+            Warning::MissingFinalNewline => write!(f, "the last line doesn't end with a newline"),
+        }
+    }
+}
+
+/// The shape of an input: how it is split into lines and blocks, what it is
+/// made of and what is wrong with it.
+#[derive(Debug, PartialEq)]
+pub struct Summary {
+    pub lines: usize,
+    /// Groups of lines separated by blank lines.
+    pub blocks: usize,
+    /// Shortest and longest non-blank line, without line endings.
+    pub line_lengths: Option<(usize, usize)>,
+    pub mean_line_length: f64,
+    /// Every char except line endings.
+    pub chars: BTreeSet<char>,
+    /// Rows and columns, when the input is a single block of at least 2
+    /// lines of the same length.
+    pub grid: Option<(usize, usize)>,
+    pub numbers: usize,
+    /// Smallest and largest number, the ones that don't fit in an `i64` aside.
+    pub number_range: Option<(i64, i64)>,
+    pub warnings: Vec<Warning>,
+}
+
+impl Summary {
+    pub fn of(input: &str) -> Self {
+        let lines: Vec<&str> = match input.strip_suffix('\n') {
+            Some(rest) => rest.split('\n').collect(),
+            None if input.is_empty() => vec![],
+            None => input.split('\n').collect(),
+        };
+        let crlf_lines = lines.iter().filter(|l| l.ends_with('\r')).count();
+        let lines: Vec<&str> = lines
+            .iter()
+            .map(|l| l.strip_suffix('\r').unwrap_or(l))
+            .collect();
+
+        let non_blank: Vec<usize> = lines
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.chars().count())
+            .collect();
+        let blocks = lines
+            .split(|l| l.trim().is_empty())
+            .filter(|block| !block.is_empty())
+            .count();
+        let line_lengths = non_blank.iter().min().zip(non_blank.iter().max());
+        let mean_line_length = match non_blank.len() {
+            0 => 0.0,
+            n => non_blank.iter().sum::<usize>() as f64 / n as f64,
+        };
+        let grid = match line_lengths {
+            Some((&min, &max)) if blocks == 1 && non_blank.len() > 1 && min == max => {
+                Some((non_blank.len(), min))
+            }
+            _ => None,
+        };
+
+        let numbers = find_numbers(input);
+        let number_range = numbers
+            .iter()
+            .flatten()
+            .min()
+            .zip(numbers.iter().flatten().max());
+
+        let mut warnings = Vec::new();
+        if input.is_empty() {
+            warnings.push(Warning::Empty);
+        }
+        if crlf_lines > 0 {
+            warnings.push(Warning::CrlfEndings { lines: crlf_lines });
+        }
+        let trailing: Vec<usize> = (1..)
+            .zip(&lines)
+            .filter(|(_, l)| l.ends_with(char::is_whitespace))
+            .map(|(i, _)| i)
+            .collect();
+        if let Some(&first) = trailing.first() {
+            warnings.push(Warning::TrailingWhitespace {
+                lines: trailing.len(),
+                first,
+            });
+        }
+        if !input.is_empty() && !input.ends_with('\n') {
+            warnings.push(Warning::MissingFinalNewline);
+        }
+
+        Self {
+            lines: lines.len(),
+            blocks,
+            line_lengths: line_lengths.map(|(&min, &max)| (min, max)),
+            mean_line_length,
+            chars: input.chars().filter(|&c| c != '\n' && c != '\r').collect(),
+            grid,
+            numbers: numbers.len(),
+            number_range: number_range.map(|(&min, &max)| (min, max)),
+            warnings,
+        }
+    }
+}
+
+/// Returns every run of digits as a number, or `None` when it doesn't fit in
+/// an `i64`. A `-` right before makes it negative, unless it separates it
+/// from another number like in `2-4`.
+fn find_numbers(input: &str) -> Vec<Option<i64>> {
+    let mut numbers = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    let (mut previous, mut negative) = (None, false);
+    while let Some((start, c)) = chars.next() {
+        if !c.is_ascii_digit() {
+            negative = c == '-' && !previous.is_some_and(|p: char| p.is_ascii_digit());
+            previous = Some(c);
+            continue;
+        }
+        let mut end = start + 1;
+        while let Some(&(i, d)) = chars.peek() {
+            if !d.is_ascii_digit() {
+                break;
+            }
+            end = i + 1;
+            chars.next();
+        }
+        let digits = &input[start..end];
+        let number = match negative {
+            true => format!("-{digits}").parse().ok(),
+            false => digits.parse().ok(),
+        };
+        numbers.push(number);
+        (previous, negative) = (digits.chars().last(), false);
+    }
+    numbers
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "lines: {} in {} blocks", self.lines, self.blocks)?;
+        if let Some((min, max)) = self.line_lengths {
+            writeln!(
+                f,
+                "line length: {min} to {max}, mean {:.1}",
+                self.mean_line_length
+            )?;
+        }
+        let chars: String = self.chars.iter().collect();
+        writeln!(f, "chars: {chars:?} ({} distinct)", self.chars.len())?;
+        if let Some((rows, cols)) = self.grid {
+            writeln!(f, "grid: {rows} rows x {cols} columns")?;
+        }
+        match self.number_range {
+            Some((min, max)) => writeln!(f, "numbers: {}, from {min} to {max}", self.numbers)?,
+            None if self.numbers > 0 => writeln!(f, "numbers: {}, all too large", self.numbers)?,
+            None => writeln!(f, "numbers: none")?,
+        }
+        for warning in &self.warnings {
+            writeln!(f, "warning: {warning}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_numbers, Summary, Warning};
+
+    #[test]
+    fn test_grid() {
+        let summary = Summary::of("30373\n25512\n65332\n");
+        assert_eq!(summary.lines, 3);
+        assert_eq!(summary.blocks, 1);
+        assert_eq!(summary.grid, Some((3, 5)));
+        assert_eq!(summary.line_lengths, Some((5, 5)));
+        assert_eq!(summary.chars.len(), 7);
+        assert_eq!(summary.number_range, Some((25512, 65332)));
+        assert!(summary.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let summary = Summary::of("1000\n2000\n\n4000\n\n5000\n6000\n");
+        assert_eq!((summary.lines, summary.blocks), (7, 3));
+        assert_eq!(summary.grid, None);
+        assert_eq!(summary.line_lengths, Some((4, 4)));
+        assert_eq!(summary.numbers, 5);
+        assert_eq!(summary.number_range, Some((1000, 6000)));
+    }
+
+    #[test]
+    fn test_warnings() {
+        let summary = Summary::of("move 1 from 2 to 1\r\nmove 3 from 1 to 3 \r\n[Z]");
+        assert_eq!(summary.lines, 3);
+        assert_eq!(summary.grid, None);
+        assert!(!summary.chars.contains(&'\r'));
+        assert_eq!(
+            summary.warnings,
+            [
+                Warning::CrlfEndings { lines: 2 },
+                Warning::TrailingWhitespace { lines: 1, first: 2 },
+                Warning::MissingFinalNewline,
+            ]
+        );
+        assert_eq!(Summary::of("").warnings, [Warning::Empty]);
+        assert_eq!(Summary::of("").lines, 0);
+    }
+
+    #[test]
+    fn test_find_numbers() {
+        assert_eq!(
+            find_numbers("x=-2, y=15: 3-4,-5"),
+            [Some(-2), Some(15), Some(3), Some(4), Some(-5)]
+        );
+        assert_eq!(find_numbers("99999999999999999999 a"), [None]);
+        assert!(find_numbers("noop").is_empty());
+    }
+}
@@ -1,15 +1,51 @@
-use std::{fs, io, path::Path};
+use std::{fs, io, path::Path, process};
 
-use clap::{Arg, Command};
+use clap::{value_parser, Arg, ArgAction, Command, ValueHint};
+use clap_complete::Shell;
+
+pub mod alloc;
+pub mod crypt;
+pub mod determinism;
+pub mod difftest;
+pub mod inputs;
+pub mod inspect;
+pub mod plugin;
+pub mod progress;
+pub mod random;
+pub mod shrink;
+pub mod sim;
+pub mod snapshot;
+pub mod solution;
+
+/// Reads the whole input file. If the file is encrypted (or only an encrypted
+/// `.age` copy of it exists), it is decrypted with the key from [`crypt::load_key`].
+pub fn read_puzzle_data<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    let path = path.as_ref();
+    let encrypted = crypt::encrypted_path(path);
+    let path = match path.exists() || !encrypted.exists() {
+        true => path,
+        false => &encrypted,
+    };
+
+    let bytes = fs::read(path)?;
+    let bytes = match crypt::is_encrypted(path) {
+        true => crypt::decrypt(&crypt::load_key()?, &bytes)?,
+        false => bytes,
+    };
+    String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
 
 pub fn read_puzzle_input<P: AsRef<Path>>(path: P) -> io::Result<Vec<String>> {
-    let string = fs::read_to_string(path)?;
-    let lines: Vec<String> = string
-        .split('\n')
+    let string = read_puzzle_data(path)?;
+    Ok(split_puzzle_input(&string))
+}
+
+/// Splits the input into trimmed lines, dropping the empty ones.
+pub fn split_puzzle_input(data: &str) -> Vec<String> {
+    data.split('\n')
         .map(|e| e.trim().to_owned())
         .filter(|s| !s.is_empty())
-        .collect();
-    Ok(lines)
+        .collect()
 }
 
 pub struct PuzzleCli {
@@ -18,12 +54,54 @@ pub struct PuzzleCli {
 
 impl PuzzleCli {
     pub fn new(name: &str) -> Self {
-        let command = Command::new(name.to_owned()).arg(Arg::new("input").required(true));
+        let command = Command::new(name.to_owned())
+            .arg(
+                Arg::new("input")
+                    .required_unless_present_any(["completions", "man"])
+                    .value_hint(ValueHint::FilePath),
+            )
+            .arg(
+                Arg::new("completions")
+                    .long("completions")
+                    .value_name("SHELL")
+                    .value_parser(value_parser!(Shell))
+                    .exclusive(true)
+                    .help("Print a completion script for the shell and exit"),
+            )
+            .arg(
+                Arg::new("man")
+                    .long("man")
+                    .action(ArgAction::SetTrue)
+                    .exclusive(true)
+                    .help("Print a man page and exit"),
+            );
+        Self { command }
+    }
+
+    /// Makes the input optional, reading `path` when it is not given.
+    pub fn with_default_input(self, path: &Path) -> Self {
+        let path = path.display().to_string();
+        let command = self.command.mut_arg("input", |_| {
+            Arg::new("input")
+                .default_value(path)
+                .value_hint(ValueHint::FilePath)
+        });
         Self { command }
     }
 
     pub fn parse_args(self) -> PuzzleArgs {
+        let mut command = self.command.clone();
         let matches = self.command.get_matches();
+        if let Some(&shell) = matches.get_one::<Shell>("completions") {
+            let name = command.get_name().to_owned();
+            clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+            process::exit(0);
+        }
+        if matches.get_flag("man") {
+            let result = clap_mangen::Man::new(command).render(&mut io::stdout());
+            process::exit(if result.is_ok() { 0 } else { 1 });
+        }
+
         let input = matches
             .get_one::<String>("input")
             .expect("required")
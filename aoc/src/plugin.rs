@@ -0,0 +1,39 @@
+use crate::solution::Solution;
+
+/// Bumped whenever [`Registry`] or [`Solution`] change. Plugins are called
+/// through the Rust ABI, so they must also be built by the same compiler.
+pub const PLUGIN_ABI_VERSION: u32 = 6;
+pub const PLUGIN_SYMBOL: &[u8] = b"aoc_plugin_registry\0";
+
+/// The solution registry a plugin library exports. `abi_version` comes first
+/// so it can be checked before anything else is read.
+#[repr(C)]
+pub struct Registry {
+    pub abi_version: u32,
+    pub year: u16,
+    pub solutions: &'static [Solution],
+}
+
+impl Registry {
+    pub const fn new(year: u16, solutions: &'static [Solution]) -> Self {
+        Self {
+            abi_version: PLUGIN_ABI_VERSION,
+            year,
+            solutions,
+        }
+    }
+}
+
+/// Exports the solutions of a year crate built as a `cdylib` so the runner
+/// can load them at runtime.
+#[macro_export]
+macro_rules! export_plugin {
+    ($year:expr, $solutions:expr) => {
+        #[no_mangle]
+        pub fn aoc_plugin_registry() -> &'static $crate::plugin::Registry {
+            static REGISTRY: $crate::plugin::Registry =
+                $crate::plugin::Registry::new($year, $solutions);
+            &REGISTRY
+        }
+    };
+}
@@ -0,0 +1,128 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How far along a long-running part is. Parts set the number of steps they
+/// are going to take and tick them off, and the runner shows it while they run:
+///
+/// ```
+/// let progress = aoc::progress::current();
+/// progress.set_total(10_000);
+/// for _ in 0..10_000 {
+///     progress.inc();
+/// }
+/// ```
+///
+/// There is one for the whole process, so parts running at the same time
+/// share it. Parts loaded from plugin libraries tick their own copy, which the
+/// runner doesn't see.
+pub struct Progress {
+    total: AtomicU64,
+    done: AtomicU64,
+    started: Mutex<Option<Instant>>,
+}
+
+static PROGRESS: Progress = Progress {
+    total: AtomicU64::new(0),
+    done: AtomicU64::new(0),
+    started: Mutex::new(None),
+};
+
+pub fn current() -> &'static Progress {
+    &PROGRESS
+}
+
+/// What a part reported so far.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Snapshot {
+    pub done: u64,
+    pub total: u64,
+    /// Time since the total was set.
+    pub elapsed: Duration,
+}
+
+impl Snapshot {
+    /// Extrapolates the time left from the steps done so far.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.done == 0 || self.done > self.total {
+            return None;
+        }
+        let left = (self.total - self.done) as f64 / self.done as f64;
+        Some(self.elapsed.mul_f64(left))
+    }
+}
+
+impl Progress {
+    /// Starts counting `total` steps from 0.
+    pub fn set_total(&self, total: u64) {
+        *self.started.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+        self.done.store(0, Ordering::Relaxed);
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    pub fn inc(&self) {
+        self.inc_by(1);
+    }
+
+    pub fn inc_by(&self, steps: u64) {
+        self.done.fetch_add(steps, Ordering::Relaxed);
+    }
+
+    /// Forgets the total, before running another part.
+    pub fn reset(&self) {
+        *self.started.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        self.total.store(0, Ordering::Relaxed);
+        self.done.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns nothing until a total is set.
+    pub fn snapshot(&self) -> Option<Snapshot> {
+        let started = (*self.started.lock().unwrap_or_else(|e| e.into_inner()))?;
+        Some(Snapshot {
+            done: self.done.load(Ordering::Relaxed),
+            total: self.total.load(Ordering::Relaxed),
+            elapsed: started.elapsed(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Progress, Snapshot};
+
+    #[test]
+    fn test_progress() {
+        let progress = Progress {
+            total: 0.into(),
+            done: 0.into(),
+            started: None.into(),
+        };
+        assert_eq!(progress.snapshot(), None);
+
+        progress.set_total(10);
+        progress.inc();
+        progress.inc_by(3);
+        let snapshot = progress.snapshot().unwrap();
+        assert_eq!((snapshot.done, snapshot.total), (4, 10));
+
+        progress.set_total(5);
+        assert_eq!(progress.snapshot().unwrap().done, 0);
+        progress.reset();
+        assert_eq!(progress.snapshot(), None);
+    }
+
+    #[test]
+    fn test_eta() {
+        let snapshot = |done| Snapshot {
+            done,
+            total: 10,
+            elapsed: Duration::from_secs(8),
+        };
+        assert_eq!(snapshot(4).eta(), Some(Duration::from_secs(12)));
+        assert_eq!(snapshot(10).eta(), Some(Duration::ZERO));
+        assert_eq!(snapshot(0).eta(), None);
+        assert_eq!(snapshot(11).eta(), None);
+    }
+}
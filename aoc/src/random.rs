@@ -0,0 +1,77 @@
+/// A small seeded random number generator (SplitMix64) for input generators.
+/// The same seed always produces the same numbers, on every platform and
+/// build, so a generated input can be reproduced from its seed alone.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..n`. `n` must not be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Returns a number in `low..=high`.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "empty range");
+        let span = high.abs_diff(low).wrapping_add(1);
+        match span {
+            0 => self.next_u64() as i64,
+            _ => low.wrapping_add((self.next_u64() % span) as i64),
+        }
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn test_same_seed_same_numbers() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_between() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.between(-3, 3)));
+        }
+        assert_eq!(rng.between(5, 5), 5);
+        rng.between(i64::MIN, i64::MAX);
+    }
+}
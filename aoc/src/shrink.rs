@@ -0,0 +1,104 @@
+use std::ops::Range;
+
+/// Makes `input` smaller for as long as `fails` still returns `true` for it.
+/// Removes blocks (separated by blank lines), then lines, then makes numbers
+/// smaller, and starts over until none of them shrinks the input any more.
+pub fn shrink(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let mut current = input.to_owned();
+    loop {
+        let before = current.clone();
+        current = remove_parts(&current, "\n\n", &mut fails);
+        current = remove_parts(&current, "\n", &mut fails);
+        current = shrink_numbers(&current, &mut fails);
+        if current == before {
+            return current;
+        }
+    }
+}
+
+/// Delta debugging: removes chunks of the parts separated by `separator`,
+/// trying smaller chunks when no chunk can be removed.
+fn remove_parts(input: &str, separator: &str, fails: &mut impl FnMut(&str) -> bool) -> String {
+    let mut parts: Vec<&str> = input.split(separator).collect();
+    let mut chunks = 2;
+    while parts.len() > 1 {
+        let chunk_len = parts.len().div_ceil(chunks);
+        let reduced = (0..parts.len()).step_by(chunk_len).find_map(|start| {
+            let end = (start + chunk_len).min(parts.len());
+            let candidate = [&parts[..start], &parts[end..]].concat();
+            fails(&candidate.join(separator)).then_some(candidate)
+        });
+
+        match reduced {
+            Some(candidate) => {
+                parts = candidate;
+                chunks = (chunks - 1).max(2);
+            }
+            None if chunk_len == 1 => break,
+            None => chunks = (chunks * 2).min(parts.len()),
+        }
+    }
+    parts.join(separator)
+}
+
+/// Replaces every number, one at a time, with the smallest of 0, 1, half of
+/// it or one less that still fails.
+fn shrink_numbers(input: &str, fails: &mut impl FnMut(&str) -> bool) -> String {
+    let mut current = input.to_owned();
+    let mut index = 0;
+    loop {
+        let Some(range) = number_ranges(&current).nth(index) else {
+            return current;
+        };
+        let Ok(value) = current[range.clone()].parse::<u64>() else {
+            index += 1;
+            continue;
+        };
+        let smaller = [0, 1, value / 2, value.saturating_sub(1)]
+            .into_iter()
+            .filter(|&v| v < value)
+            .map(|v| format!("{}{v}{}", &current[..range.start], &current[range.end..]))
+            .find(|candidate| fails(candidate));
+        match smaller {
+            Some(candidate) => current = candidate,
+            None => index += 1,
+        }
+    }
+}
+
+fn number_ranges(input: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let bytes = input.as_bytes();
+    (0..bytes.len())
+        .filter(move |&i| bytes[i].is_ascii_digit() && (i == 0 || !bytes[i - 1].is_ascii_digit()))
+        .map(move |start| {
+            let len = bytes[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            start..start + len
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::shrink;
+
+    #[test]
+    fn test_remove_lines_and_blocks() {
+        let input = "a\nb\nc\n\nd\nbad\ne\n\nf\n";
+        assert_eq!(shrink(input, |s| s.contains("bad")), "bad");
+
+        let input = "x\nbad 1\ny\nbad 2\nz";
+        let both = |s: &str| s.contains("bad 1") && s.contains("bad 2");
+        assert_eq!(shrink(input, both), "bad 1\nbad 2");
+    }
+
+    #[test]
+    fn test_shrink_numbers() {
+        let fails = |s: &str| s.lines().any(|l| l.parse::<u32>().is_ok_and(|n| n >= 10));
+        assert_eq!(shrink("3\n57\n8\n", fails), "10");
+
+        let fails = |s: &str| s.split(',').map(|n| n.parse::<u32>().unwrap()).sum::<u32>() > 100;
+        assert_eq!(shrink("7,1000,3", fails), "0,98,3");
+    }
+}
@@ -0,0 +1,193 @@
+use std::io::{BufRead, Write};
+
+use anyhow::{bail, Context, Result};
+
+/// A puzzle that plays out one step at a time, like a rope being pulled or
+/// sand being poured. [`Driver`] runs it, and can record its states to a
+/// trace that [`replay`] plays back later.
+pub trait Simulation {
+    /// Everything that changes while the simulation runs, encoded on a single
+    /// line that [`Simulation::restore`] reads back.
+    fn state(&self) -> String;
+
+    fn restore(&mut self, state: &str) -> Result<()>;
+
+    /// Advances by one step. Does nothing once the simulation is done.
+    fn step(&mut self);
+
+    fn done(&self) -> bool;
+
+    /// Draws the current state over several lines.
+    fn render(&self) -> String;
+
+    /// Lines and columns of the chars of [`Simulation::render`] showing what
+    /// moves, like the falling sand.
+    fn highlighted(&self) -> Vec<(usize, usize)> {
+        vec![]
+    }
+
+    /// Named numbers describing the current state, that breakpoints of
+    /// `aoc debug` can test.
+    fn values(&self) -> Vec<(&'static str, i64)> {
+        vec![]
+    }
+}
+
+/// Creates the simulation of a part from the raw puzzle input.
+pub type SimulationLoader = fn(&str, u8) -> Result<Box<dyn Simulation>>;
+
+/// Runs a simulation, writing its state to the trace after every step when
+/// recording.
+pub struct Driver<'a> {
+    simulation: Box<dyn Simulation>,
+    steps: usize,
+    trace: Option<Box<dyn Write + 'a>>,
+}
+
+impl<'a> Driver<'a> {
+    pub fn new(simulation: Box<dyn Simulation>) -> Self {
+        Self {
+            simulation,
+            steps: 0,
+            trace: None,
+        }
+    }
+
+    /// Records the states to `trace`, one per line, starting with the current one.
+    pub fn record(&mut self, trace: impl Write + 'a) -> Result<()> {
+        let mut trace: Box<dyn Write + 'a> = Box::new(trace);
+        writeln!(trace, "{}", self.simulation.state())?;
+        self.trace = Some(trace);
+        Ok(())
+    }
+
+    /// Returns false without stepping if the simulation is done.
+    pub fn step(&mut self) -> Result<bool> {
+        if self.simulation.done() {
+            return Ok(false);
+        }
+        self.simulation.step();
+        self.steps += 1;
+        if let Some(trace) = &mut self.trace {
+            writeln!(trace, "{}", self.simulation.state())?;
+        }
+        Ok(true)
+    }
+
+    /// Runs at most `steps` steps and returns how many were run.
+    pub fn run_steps(&mut self, steps: usize) -> Result<usize> {
+        let mut run = 0;
+        while run < steps && self.step()? {
+            run += 1;
+        }
+        self.flush()?;
+        Ok(run)
+    }
+
+    /// Runs until the simulation is done and returns the number of steps run.
+    pub fn run(&mut self) -> Result<usize> {
+        self.run_steps(usize::MAX)
+    }
+
+    /// Steps run since the driver was created.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn simulation(&self) -> &dyn Simulation {
+        self.simulation.as_ref()
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if let Some(trace) = &mut self.trace {
+            trace.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the states of a trace written by [`Driver::record`].
+pub fn read_trace(trace: impl BufRead) -> Result<Vec<String>> {
+    let states = trace.lines().collect::<Result<Vec<_>, _>>()?;
+    if states.is_empty() {
+        bail!("the trace is empty");
+    }
+    Ok(states)
+}
+
+/// Restores the simulation to every state of the trace in turn and returns
+/// how it renders in each of them.
+pub fn replay(simulation: &mut dyn Simulation, states: &[String]) -> Result<Vec<String>> {
+    let mut frames = Vec::with_capacity(states.len());
+    for (step, state) in states.iter().enumerate() {
+        simulation
+            .restore(state)
+            .with_context(|| format!("restoring step {step} of the trace"))?;
+        frames.push(simulation.render());
+    }
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::{read_trace, replay, Driver, Simulation};
+
+    /// Counts down to 0.
+    struct Countdown(u32);
+
+    impl Simulation for Countdown {
+        fn state(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn restore(&mut self, state: &str) -> Result<()> {
+            self.0 = state.parse()?;
+            Ok(())
+        }
+
+        fn step(&mut self) {
+            self.0 = self.0.saturating_sub(1);
+        }
+
+        fn done(&self) -> bool {
+            self.0 == 0
+        }
+
+        fn render(&self) -> String {
+            format!("{}\n", "#".repeat(self.0 as usize))
+        }
+    }
+
+    #[test]
+    fn test_driver() -> Result<()> {
+        let mut driver = Driver::new(Box::new(Countdown(5)));
+        assert_eq!(driver.run_steps(2)?, 2);
+        assert_eq!(driver.simulation().state(), "3");
+        assert_eq!(driver.run()?, 3);
+        assert_eq!(driver.run_steps(2)?, 0);
+        assert_eq!(driver.steps(), 5);
+        assert!(driver.simulation().done());
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_and_replay() -> Result<()> {
+        let mut trace = Vec::new();
+        let mut driver = Driver::new(Box::new(Countdown(3)));
+        driver.record(&mut trace)?;
+        driver.run()?;
+        drop(driver);
+        assert_eq!(String::from_utf8(trace.clone())?, "3\n2\n1\n0\n");
+
+        let states = read_trace(trace.as_slice())?;
+        let frames = replay(&mut Countdown(0), &states)?;
+        assert_eq!(frames, ["###\n", "##\n", "#\n", "\n"]);
+
+        let error = replay(&mut Countdown(0), &["2".into(), "x".into()]).unwrap_err();
+        assert_eq!(error.to_string(), "restoring step 1 of the trace");
+        assert!(read_trace(&b""[..]).is_err());
+        Ok(())
+    }
+}
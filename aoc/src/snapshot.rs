@@ -0,0 +1,163 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::inputs::examples_dir;
+
+pub const SNAPSHOTS_DIR: &str = "snapshots";
+pub const SNAPSHOT_EXTENSION: &str = "snap";
+/// Added to the snapshot path for outputs that don't match it yet.
+pub const PENDING_EXTENSION: &str = "new";
+
+/// Checks a rendered output against the snapshot `name` of the calling crate,
+/// stored in `test_data/snapshots`. A mismatching output is saved as pending
+/// and the test fails with a diff, until it is accepted with
+/// `aoc snapshots accept`.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        let dir = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test_data")
+            .join($crate::snapshot::SNAPSHOTS_DIR);
+        if let Err(message) = $crate::snapshot::check(&dir, $name, &$actual) {
+            panic!("{message}");
+        }
+    };
+}
+
+pub fn snapshots_dir(year: u16) -> PathBuf {
+    examples_dir(year).join(SNAPSHOTS_DIR)
+}
+
+pub fn snapshot_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.{SNAPSHOT_EXTENSION}"))
+}
+
+pub fn pending_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.{SNAPSHOT_EXTENSION}.{PENDING_EXTENSION}"))
+}
+
+/// Compares `actual` with the snapshot and returns a description of the
+/// difference if they don't match, after saving `actual` as pending.
+pub fn check(dir: &Path, name: &str, actual: &str) -> Result<(), String> {
+    let pending = pending_path(dir, name);
+    let expected = fs::read_to_string(snapshot_path(dir, name)).ok();
+    if expected.as_deref() == Some(actual) {
+        let _ = fs::remove_file(&pending);
+        return Ok(());
+    }
+
+    let saved = fs::create_dir_all(dir).and_then(|_| fs::write(&pending, actual));
+    if let Err(e) = saved {
+        return Err(format!("can't save {}: {e}", pending.display()));
+    }
+    let change = match &expected {
+        Some(expected) => format!("doesn't match:\n{}", diff(expected, actual)),
+        None => format!("is new:\n{}", diff("", actual)),
+    };
+    Err(format!(
+        "snapshot {name} {change}\nSaved to {}, run `aoc snapshots accept` to accept it",
+        pending.display()
+    ))
+}
+
+/// Returns the names of the snapshots with a pending output, sorted.
+pub fn pending(dir: &Path) -> io::Result<Vec<String>> {
+    let suffix = format!(".{SNAPSHOT_EXTENSION}.{PENDING_EXTENSION}");
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+        let file_name = entry?.file_name();
+        if let Some(name) = file_name.to_string_lossy().strip_suffix(&suffix) {
+            names.push(name.to_string());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Replaces the snapshot with its pending output.
+pub fn accept(dir: &Path, name: &str) -> io::Result<()> {
+    fs::rename(pending_path(dir, name), snapshot_path(dir, name))
+}
+
+pub fn reject(dir: &Path, name: &str) -> io::Result<()> {
+    fs::remove_file(pending_path(dir, name))
+}
+
+/// Line by line diff, with removed lines marked `-`, added ones `+`, and the
+/// others indented to match.
+pub fn diff(old: &str, new: &str) -> String {
+    let (old, new): (Vec<&str>, Vec<&str>) = (old.lines().collect(), new.lines().collect());
+
+    // Longest common subsequence of lines, starting from the ends
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = match old[i] == new[j] {
+                true => common[i + 1][j + 1] + 1,
+                false => common[i + 1][j].max(common[i][j + 1]),
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push_str(&format!("  {}\n", old[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            diff.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        } else {
+            diff.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{accept, check, diff, pending, pending_path, reject, snapshot_path};
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("a\nb\nc\n", "a\nx\nc\n"), "  a\n- b\n+ x\n  c\n");
+        assert_eq!(diff("", "a\nb"), "+ a\n+ b\n");
+        assert_eq!(diff("a\nb", "b"), "- a\n  b\n");
+        assert_eq!(diff("#..\n.#.\n", "#..\n.#.\n"), "  #..\n  .#.\n");
+    }
+
+    #[test]
+    fn test_check_and_accept() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+
+        let message = check(dir, "screen", "##\n..\n").unwrap_err();
+        assert!(message.contains("snapshot screen is new:\n+ ##\n+ ..\n"));
+        assert_eq!(pending(dir).unwrap(), ["screen"]);
+        accept(dir, "screen").unwrap();
+        assert_eq!(
+            fs::read_to_string(snapshot_path(dir, "screen")).unwrap(),
+            "##\n..\n"
+        );
+        assert!(check(dir, "screen", "##\n..\n").is_ok());
+
+        let message = check(dir, "screen", "##\n.#\n").unwrap_err();
+        assert!(message.contains("doesn't match:\n  ##\n- ..\n+ .#\n"));
+        reject(dir, "screen").unwrap();
+        assert!(pending(dir).unwrap().is_empty());
+
+        check(dir, "screen", "#\n").unwrap_err();
+        assert!(check(dir, "screen", "##\n..\n").is_ok());
+        assert!(!pending_path(dir, "screen").exists());
+    }
+}
@@ -0,0 +1,143 @@
+use anyhow::Result;
+
+use crate::random::Rng;
+use crate::sim::SimulationLoader;
+
+pub const MAIN_VARIANT: &str = "main";
+pub const REFERENCE_VARIANT: &str = "reference";
+
+/// Solves one part of a puzzle for the given raw input.
+pub type Part = fn(&str) -> Result<String>;
+
+/// Generates a random valid input. What `size` counts (lines, items, grid
+/// cells...) depends on the puzzle.
+pub type Generator = fn(usize, &mut Rng) -> String;
+
+/// Another implementation of one part, kept next to the main one to compare
+/// their answers and timings.
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: Part,
+}
+
+impl Variant {
+    pub const fn new(name: &'static str, part: u8, solve: Part) -> Self {
+        Self { name, part, solve }
+    }
+}
+
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub parts: &'static [Part],
+    pub generator: Option<Generator>,
+    /// Slow but obviously correct implementations of the optimised parts,
+    /// indexed like `parts`.
+    pub references: &'static [Option<Part>],
+    pub variants: &'static [Variant],
+    /// Plays the puzzle out step by step, see `aoc sim`.
+    pub simulation: Option<SimulationLoader>,
+}
+
+impl Solution {
+    pub const fn new(year: u16, day: u8, parts: &'static [Part]) -> Self {
+        Self {
+            year,
+            day,
+            parts,
+            generator: None,
+            references: &[],
+            variants: &[],
+            simulation: None,
+        }
+    }
+
+    pub const fn with_generator(self, generator: Generator) -> Self {
+        Self {
+            generator: Some(generator),
+            ..self
+        }
+    }
+
+    pub const fn with_references(self, references: &'static [Option<Part>]) -> Self {
+        Self { references, ..self }
+    }
+
+    pub const fn with_variants(self, variants: &'static [Variant]) -> Self {
+        Self { variants, ..self }
+    }
+
+    pub const fn with_simulation(self, simulation: SimulationLoader) -> Self {
+        Self {
+            simulation: Some(simulation),
+            ..self
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<Part> {
+        let index = usize::from(part).checked_sub(1)?;
+        self.parts.get(index).copied()
+    }
+
+    pub fn reference(&self, part: u8) -> Option<Part> {
+        let index = usize::from(part).checked_sub(1)?;
+        self.references.get(index).copied().flatten()
+    }
+
+    /// Returns every implementation of the part by name: the main one first,
+    /// then the reference and the registered variants.
+    pub fn variants(&self, part: u8) -> Vec<(&'static str, Part)> {
+        let Some(main) = self.part(part) else {
+            return vec![];
+        };
+        let mut variants = vec![(MAIN_VARIANT, main)];
+        variants.extend(self.reference(part).map(|r| (REFERENCE_VARIANT, r)));
+        variants.extend(
+            self.variants
+                .iter()
+                .filter(|v| v.part == part)
+                .map(|v| (v.name, v.solve)),
+        );
+        variants
+    }
+}
+
+pub fn find_solution(solutions: &[Solution], day: u8) -> Option<&Solution> {
+    solutions.iter().find(|s| s.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::{Solution, Variant};
+
+    fn one(_: &str) -> Result<String> {
+        Ok("1".to_string())
+    }
+
+    fn two(_: &str) -> Result<String> {
+        Ok("2".to_string())
+    }
+
+    const VARIANTS: &[Variant] = &[Variant::new("fast", 2, two), Variant::new("slow", 2, two)];
+
+    #[test]
+    fn test_variants() {
+        let solution = Solution::new(2022, 1, &[one, two])
+            .with_references(&[None, Some(two)])
+            .with_variants(VARIANTS);
+        let names = |part| -> Vec<&str> {
+            solution
+                .variants(part)
+                .iter()
+                .map(|&(name, _)| name)
+                .collect()
+        };
+        assert_eq!(names(1), ["main"]);
+        assert_eq!(names(2), ["main", "reference", "fast", "slow"]);
+        assert!(names(3).is_empty());
+    }
+}
@@ -0,0 +1,37 @@
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Generates the header into `OUT_DIR`. The copy in `include` is checked
+/// against it by the tests.
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let config = cbindgen::Config::from_file(Path::new(&crate_dir).join("cbindgen.toml"))
+        .expect("invalid cbindgen.toml");
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("unable to generate the header")
+        .write_to_file(Path::new(&out_dir).join("aoc.h"));
+
+    // The workspace builds with `-Cprefer-dynamic`, so the library links the
+    // toolchain's libstd. Record where it is, so ctypes and C programs can load
+    // the library without setting LD_LIBRARY_PATH.
+    if env::var("CARGO_CFG_TARGET_FAMILY").as_deref() == Ok("unix") {
+        let rustc = env::var("RUSTC").unwrap();
+        let output = Command::new(rustc)
+            .args(["--print", "sysroot"])
+            .output()
+            .expect("unable to run rustc");
+        let sysroot = String::from_utf8(output.stdout).expect("invalid sysroot");
+        let std_dir = Path::new(sysroot.trim())
+            .join("lib/rustlib")
+            .join(env::var("TARGET").unwrap())
+            .join("lib");
+        println!(
+            "cargo:rustc-cdylib-link-arg=-Wl,-rpath,{}",
+            std_dir.display()
+        );
+    }
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,69 @@
+#ifndef AOC_CAPI_H
+#define AOC_CAPI_H
+
+/* Generated from capi/src/lib.rs by `AOC_UPDATE_HEADER=1 cargo test -p capi`, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result of [`aoc_solve`].
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  /**
+   * The year, day or part is not implemented.
+   */
+  AOC_STATUS_UNKNOWN_PUZZLE = 1,
+  /**
+   * The input is not valid UTF-8.
+   */
+  AOC_STATUS_INVALID_INPUT = 2,
+  /**
+   * The solution failed, the buffer holds the error message, cut to fit.
+   */
+  AOC_STATUS_SOLVE_ERROR = 3,
+  /**
+   * The solution panicked.
+   */
+  AOC_STATUS_PANIC = 4,
+  /**
+   * The buffer is too small, it needs `len + 1` bytes.
+   */
+  AOC_STATUS_BUFFER_TOO_SMALL = 5,
+  /**
+   * A required pointer is null.
+   */
+  AOC_STATUS_NULL_POINTER = 6,
+} AocStatus;
+
+/**
+ * A buffer owned by the caller. `len` is set to the length of the written
+ * string, without the terminating NUL byte.
+ */
+typedef struct AocBuffer {
+  uint8_t *data;
+  size_t capacity;
+  size_t len;
+} AocBuffer;
+
+/**
+ * Solves one part of a puzzle and writes the answer to `out_buf` as a
+ * NUL-terminated string.
+ *
+ * # Safety
+ *
+ * `input_ptr` must point to `len` readable bytes, and `out_buf` to a buffer
+ * whose `data` points to `capacity` writable bytes.
+ */
+enum AocStatus aoc_solve(uint16_t year,
+                         uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input_ptr,
+                         size_t len,
+                         struct AocBuffer *out_buf);
+
+#endif  /* AOC_CAPI_H */
@@ -0,0 +1,177 @@
+//! C ABI for the solutions, see `include/aoc.h`.
+//!
+//! The library links the toolchain's libstd dynamically and finds it through
+//! its runpath. Copied to a machine without the toolchain, it needs that
+//! libstd in `LD_LIBRARY_PATH`.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice, str};
+
+use aoc::solution::{find_solution, Part};
+
+/// Result of [`aoc_solve`].
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    /// The year, day or part is not implemented.
+    UnknownPuzzle = 1,
+    /// The input is not valid UTF-8.
+    InvalidInput = 2,
+    /// The solution failed, the buffer holds the error message, cut to fit.
+    SolveError = 3,
+    /// The solution panicked.
+    Panic = 4,
+    /// The buffer is too small, it needs `len + 1` bytes.
+    BufferTooSmall = 5,
+    /// A required pointer is null.
+    NullPointer = 6,
+}
+
+/// A buffer owned by the caller. `len` is set to the length of the written
+/// string, without the terminating NUL byte.
+#[repr(C)]
+pub struct AocBuffer {
+    pub data: *mut u8,
+    pub capacity: usize,
+    pub len: usize,
+}
+
+fn find_part(year: u16, day: u8, part: u8) -> Option<Part> {
+    let solutions = match year {
+        year2022::YEAR => year2022::SOLUTIONS,
+        _ => return None,
+    };
+    find_solution(solutions, day)?.part(part)
+}
+
+/// Solves one part of a puzzle and writes the answer to `out_buf` as a
+/// NUL-terminated string.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `len` readable bytes, and `out_buf` to a buffer
+/// whose `data` points to `capacity` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u16,
+    day: u8,
+    part: u8,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut AocBuffer,
+) -> AocStatus {
+    let Some(out) = out_buf.as_mut() else {
+        return AocStatus::NullPointer;
+    };
+    out.len = 0;
+    if input_ptr.is_null() && len > 0 {
+        return AocStatus::NullPointer;
+    }
+    let Some(solve) = find_part(year, day, part) else {
+        return AocStatus::UnknownPuzzle;
+    };
+    let input = match len {
+        0 => "",
+        _ => match str::from_utf8(slice::from_raw_parts(input_ptr, len)) {
+            Ok(input) => input,
+            Err(_) => return AocStatus::InvalidInput,
+        },
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| solve(input))) {
+        Ok(Ok(answer)) => write_string(out, &answer, AocStatus::Ok),
+        Ok(Err(e)) => write_truncated(out, &format!("{e:#}"), AocStatus::SolveError),
+        Err(_) => AocStatus::Panic,
+    }
+}
+
+unsafe fn write_string(out: &mut AocBuffer, s: &str, status: AocStatus) -> AocStatus {
+    out.len = s.len();
+    if out.data.is_null() || out.capacity <= s.len() {
+        return AocStatus::BufferTooSmall;
+    }
+    ptr::copy_nonoverlapping(s.as_ptr(), out.data, s.len());
+    *out.data.add(s.len()) = 0;
+    status
+}
+
+/// Writes as much of `s` as fits, cut at a char boundary, and returns `status`
+/// even if nothing fits.
+unsafe fn write_truncated(out: &mut AocBuffer, s: &str, status: AocStatus) -> AocStatus {
+    if out.data.is_null() || out.capacity == 0 {
+        return status;
+    }
+    let mut len = s.len().min(out.capacity - 1);
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    write_string(out, &s[..len], status)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    fn solve(year: u16, day: u8, part: u8, input: &[u8], capacity: usize) -> (AocStatus, String) {
+        let mut data = vec![0xff; capacity];
+        let mut out = AocBuffer {
+            data: data.as_mut_ptr(),
+            capacity,
+            len: 0,
+        };
+        let status = unsafe { aoc_solve(year, day, part, input.as_ptr(), input.len(), &mut out) };
+        let answer = match status {
+            AocStatus::Ok | AocStatus::SolveError => {
+                assert_eq!(data[out.len], 0);
+                String::from_utf8(data[..out.len].to_vec()).unwrap()
+            }
+            _ => out.len.to_string(),
+        };
+        (status, answer)
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = b"A Y\nB X\nC Z\n";
+        assert_eq!(solve(2022, 2, 1, input, 16), (AocStatus::Ok, "15".into()));
+        assert_eq!(solve(2022, 2, 2, input, 16), (AocStatus::Ok, "12".into()));
+        assert_eq!(
+            solve(2022, 2, 1, input, 2),
+            (AocStatus::BufferTooSmall, "2".into())
+        );
+        assert_eq!(solve(2022, 2, 3, input, 16).0, AocStatus::UnknownPuzzle);
+        assert_eq!(solve(2015, 2, 1, input, 16).0, AocStatus::UnknownPuzzle);
+        assert_eq!(solve(2022, 2, 1, b"\xff", 16).0, AocStatus::InvalidInput);
+        let error = solve(2022, 10, 1, b"jump 3\n", 256);
+        assert_eq!(
+            error,
+            (AocStatus::SolveError, "undefined instruction".into())
+        );
+        let error = solve(2022, 10, 1, b"jump 3\n", 10);
+        assert_eq!(error, (AocStatus::SolveError, "undefined".into()));
+        assert_eq!(
+            solve(2022, 10, 1, b"jump 3\n", 1),
+            (AocStatus::SolveError, "".into())
+        );
+
+        let status = unsafe { aoc_solve(2022, 2, 1, input.as_ptr(), input.len(), ptr::null_mut()) };
+        assert_eq!(status, AocStatus::NullPointer);
+    }
+
+    #[test]
+    fn test_header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc.h"));
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/include/aoc.h");
+        if env::var_os("AOC_UPDATE_HEADER").is_some() {
+            fs::write(path, generated).unwrap();
+        }
+        let checked_in = fs::read_to_string(path).unwrap();
+        assert!(
+            checked_in == generated,
+            "include/aoc.h is out of date, run `AOC_UPDATE_HEADER=1 cargo test -p capi`"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Loads the library from Python with ctypes, the way our notebooks do.
+
+use std::env::consts::DLL_SUFFIX;
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde_json::Value;
+
+const SCRIPT: &str = r#"
+import ctypes
+import sys
+
+class AocBuffer(ctypes.Structure):
+    _fields_ = [
+        ("data", ctypes.POINTER(ctypes.c_uint8)),
+        ("capacity", ctypes.c_size_t),
+        ("len", ctypes.c_size_t),
+    ]
+
+aoc = ctypes.CDLL(sys.argv[1])
+aoc.aoc_solve.argtypes = [
+    ctypes.c_uint16,
+    ctypes.c_uint8,
+    ctypes.c_uint8,
+    ctypes.c_char_p,
+    ctypes.c_size_t,
+    ctypes.POINTER(AocBuffer),
+]
+aoc.aoc_solve.restype = ctypes.c_int
+
+data = (ctypes.c_uint8 * 16)()
+out = AocBuffer(data, len(data), 0)
+guide = b"A Y\nB X\nC Z\n"
+status = aoc.aoc_solve(2022, 2, 1, guide, len(guide), ctypes.byref(out))
+print(status, bytes(data[: out.len]).decode())
+"#;
+
+/// Builds the library, which `cargo test` doesn't since nothing links it.
+fn build_library() -> PathBuf {
+    let output = Command::new(env!("CARGO"))
+        .args(["build", "--package", "capi", "--lib"])
+        .args(["--message-format", "json"])
+        .args((!cfg!(debug_assertions)).then_some("--release"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let messages = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap());
+    messages
+        .filter(|message| message["target"]["name"] == "aoc_capi")
+        .flat_map(|message| message["filenames"].as_array().cloned().unwrap_or_default())
+        .filter_map(|filename| filename.as_str().map(PathBuf::from))
+        .find(|filename| filename.to_string_lossy().ends_with(DLL_SUFFIX))
+        .expect("cargo built no aoc_capi library")
+}
+
+#[test]
+fn test_ctypes() {
+    // Cargo points LD_LIBRARY_PATH at the toolchain for its tests, which users don't have
+    let output = Command::new("python3")
+        .arg("-c")
+        .arg(SCRIPT)
+        .arg(build_library())
+        .env_remove("LD_LIBRARY_PATH")
+        .env_remove("DYLD_LIBRARY_PATH")
+        .output()
+        .expect("python3 runs the ctypes test");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0 15\n");
+}
@@ -0,0 +1,66 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+pub const ATTEMPT_LOG: &str = ".aoc-cache/attempts.jsonl";
+
+/// An answer produced by `run` or `verify`, one JSON object per line of the log.
+#[derive(Serialize, Deserialize)]
+pub struct Attempt {
+    pub time: i64,
+    pub year: u16,
+    pub day: u8,
+    pub label: String,
+    pub part: u8,
+    pub answer: String,
+    /// `None` if the input has no expected answer for the part.
+    pub correct: Option<bool>,
+}
+
+impl Attempt {
+    pub fn new(
+        year: u16,
+        day: u8,
+        label: &str,
+        part: u8,
+        answer: &str,
+        expected: Option<&String>,
+    ) -> Self {
+        Self {
+            time: Utc::now().timestamp(),
+            year,
+            day,
+            label: label.to_owned(),
+            part,
+            answer: answer.to_owned(),
+            correct: expected.map(|e| e == answer),
+        }
+    }
+}
+
+pub fn record(attempt: &Attempt) -> Result<()> {
+    let path = Path::new(ATTEMPT_LOG);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(attempt)?)?;
+    Ok(())
+}
+
+pub fn load() -> Result<Vec<Attempt>> {
+    let log = match fs::read_to_string(ATTEMPT_LOG) {
+        Ok(log) => log,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    log.lines()
+        .filter(|l| !l.trim().is_empty())
+        .enumerate()
+        .map(|(i, l)| serde_json::from_str(l).with_context(|| format!("{ATTEMPT_LOG}:{}", i + 1)))
+        .collect()
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{value_parser, Arg, ArgMatches, Command};
+
+use crate::cli;
+
+pub fn command() -> Command {
+    Command::new("bench")
+        .about("Measure how long solutions take on the inputs of the day")
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().help("Benchmark only this day, all implemented days by default"))
+        .arg(cli::label_arg())
+        .arg(cli::all_variants_arg())
+        .arg(
+            Arg::new("iterations")
+                .long("iterations")
+                .short('n')
+                .value_parser(value_parser!(u32).range(1..))
+                .default_value("10"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let iterations = *matches.get_one::<u32>("iterations").expect("has default");
+    let all_variants = matches.get_flag("all-variants");
+
+    for solution in cli::selected_solutions(matches)? {
+        for input in cli::selected_inputs(solution.day, matches)? {
+            let data = input.read()?;
+            println!("Day {} [{}]", solution.day, input.label);
+
+            for part in 1..=solution.parts.len() as u8 {
+                let mut main_mean = None;
+                for (name, solve) in cli::part_variants(solution, part, all_variants) {
+                    let mut timings = Vec::new();
+                    for _ in 0..iterations {
+                        let (result, elapsed) = cli::timed(|| solve(&data));
+                        result?;
+                        timings.push(elapsed);
+                    }
+                    let total: Duration = timings.iter().sum();
+                    let mean = total / iterations;
+                    let prefix = match all_variants {
+                        true => format!("Part {part} [{name}]"),
+                        false => format!("Part {part}"),
+                    };
+                    let relative = match main_mean {
+                        Some(main) => format!(", {:.2}x main", mean.div_duration_f64(main)),
+                        None => String::new(),
+                    };
+                    println!(
+                        "  {prefix}: mean {mean:?}, min {:?}, max {:?}{relative}",
+                        timings.iter().min().expect("at least one iteration"),
+                        timings.iter().max().expect("at least one iteration"),
+                    );
+                    main_mean.get_or_insert(mean);
+                }
+            }
+        }
+    }
+    Ok(())
+}
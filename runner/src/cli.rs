@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+use aoc::inputs::{self, Input};
+use aoc::solution::{find_solution, Part, Solution};
+use clap::{value_parser, Arg, ArgAction, ArgMatches};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+
+use crate::plugin;
+
+pub fn year_arg() -> Arg {
+    Arg::new("year")
+        .required(true)
+        .value_parser(value_parser!(u16))
+        .add(ArgValueCandidates::new(year_candidates))
+}
+
+pub fn day_arg() -> Arg {
+    Arg::new("day")
+        .value_parser(value_parser!(u8).range(1..=25))
+        .add(ArgValueCandidates::new(day_candidates))
+}
+
+fn year_candidates() -> Vec<CompletionCandidate> {
+    YEARS
+        .iter()
+        .map(|year| CompletionCandidate::new(year.to_string()))
+        .collect()
+}
+
+/// Offers the implemented days, looked up when completing. Candidates don't
+/// see the other arguments, so the days of every year are offered, with their
+/// years as help.
+fn day_candidates() -> Vec<CompletionCandidate> {
+    let mut days: BTreeMap<u8, Vec<u16>> = BTreeMap::new();
+    for &year in YEARS {
+        for solution in solutions_of(year).unwrap_or_default() {
+            days.entry(solution.day).or_default().push(year);
+        }
+    }
+    days.into_iter()
+        .map(|(day, years)| {
+            let years: Vec<String> = years.iter().map(u16::to_string).collect();
+            let help = format!("year {}", years.join(", "));
+            CompletionCandidate::new(day.to_string()).help(Some(help.into()))
+        })
+        .collect()
+}
+
+/// Offers the labels of the inputs stored in the data directory when completing.
+fn label_candidates() -> Vec<CompletionCandidate> {
+    let mut labels: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    for day in 1..=25 {
+        for input in inputs::day_inputs(day).unwrap_or_default() {
+            labels.entry(input.label).or_default().push(day);
+        }
+    }
+    labels
+        .into_iter()
+        .map(|(label, days)| {
+            let days: Vec<String> = days.iter().map(u8::to_string).collect();
+            let help = format!("day {}", days.join(", "));
+            CompletionCandidate::new(label).help(Some(help.into()))
+        })
+        .collect()
+}
+
+pub fn label_arg() -> Arg {
+    Arg::new("label")
+        .long("label")
+        .short('l')
+        .add(ArgValueCandidates::new(label_candidates))
+        .help("Use only the input with this label, all inputs of the day by default")
+}
+
+pub fn all_variants_arg() -> Arg {
+    Arg::new("all-variants")
+        .long("all-variants")
+        .action(ArgAction::SetTrue)
+        .help("Also run the reference and the other variants of every part")
+}
+
+/// Returns the implementations of the part to run, only the main one unless
+/// all variants are asked for.
+pub fn part_variants(solution: &Solution, part: u8, all: bool) -> Vec<(&'static str, Part)> {
+    let mut variants = solution.variants(part);
+    if !all {
+        variants.truncate(1);
+    }
+    variants
+}
+
+pub const YEARS: &[u16] = &[year2022::YEAR];
+
+pub fn solutions_of(year: u16) -> Option<&'static [Solution]> {
+    match year {
+        year2022::YEAR => Some(year2022::SOLUTIONS),
+        _ => None,
+    }
+}
+
+/// Returns the solutions of every year, from `--plugin` libraries when given
+/// and built-in otherwise.
+pub fn all_solutions(matches: &ArgMatches) -> Result<Vec<(u16, &'static [Solution])>> {
+    let mut all: Vec<(u16, &'static [Solution])> = Vec::new();
+    for path in matches.get_many::<PathBuf>("plugin").into_iter().flatten() {
+        let registry = plugin::load(path)?;
+        if all.iter().all(|&(year, _)| year != registry.year) {
+            all.push((registry.year, registry.solutions));
+        }
+    }
+    for &year in YEARS {
+        if all.iter().any(|&(y, _)| y == year) {
+            continue;
+        }
+        if let Some(solutions) = solutions_of(year) {
+            all.push((year, solutions));
+        }
+    }
+    all.sort_by_key(|&(year, _)| year);
+    Ok(all)
+}
+
+pub fn year_solutions(matches: &ArgMatches) -> Result<&'static [Solution]> {
+    let year = *matches.get_one::<u16>("year").expect("required");
+    match all_solutions(matches)?
+        .into_iter()
+        .find(|&(y, _)| y == year)
+    {
+        Some((_, solutions)) => Ok(solutions),
+        None => bail!("no solutions for year {year}"),
+    }
+}
+
+/// Returns the solution of the selected day, or all solutions of the year if no day is given.
+pub fn selected_solutions(matches: &ArgMatches) -> Result<Vec<&'static Solution>> {
+    let solutions = year_solutions(matches)?;
+    match matches.get_one::<u8>("day") {
+        None => Ok(solutions.iter().collect()),
+        Some(&day) => match find_solution(solutions, day) {
+            Some(solution) => Ok(vec![solution]),
+            None => bail!("day {day} is not implemented"),
+        },
+    }
+}
+
+pub fn selected_inputs(day: u8, matches: &ArgMatches) -> Result<Vec<Input>> {
+    let mut inputs = inputs::day_inputs(day)?;
+    if let Some(label) = matches.get_one::<String>("label") {
+        inputs.retain(|i| &i.label == label);
+        if inputs.is_empty() {
+            bail!("day {day} has no input labeled {label}");
+        }
+    }
+    Ok(inputs)
+}
+
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
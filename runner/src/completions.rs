@@ -0,0 +1,39 @@
+use std::{env, io};
+
+use anyhow::{Context, Result};
+use clap::builder::PossibleValuesParser;
+use clap::{Arg, ArgMatches, Command};
+use clap_complete::env::Shells;
+
+/// Environment variable that makes the runner complete its arguments.
+pub const COMPLETE_ENV: &str = "COMPLETE";
+
+pub fn command() -> Command {
+    Command::new("completions")
+        .about("Print a shell completion script")
+        .long_about(
+            "Print a shell completion script. The script asks the runner for completions, \
+             so days are completed from the implemented solutions and input labels from \
+             the data directory of the current directory.",
+        )
+        .arg(
+            Arg::new("shell")
+                .required(true)
+                .value_parser(PossibleValuesParser::new(Shells::builtins().names())),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let shell = matches.get_one::<String>("shell").expect("required");
+    let shells = Shells::builtins();
+    let completer = shells.completer(shell).expect("possible value");
+    let exe = env::current_exe().context("locating the runner")?;
+    completer.write_registration(
+        COMPLETE_ENV,
+        "aoc",
+        "aoc",
+        &exe.to_string_lossy(),
+        &mut io::stdout(),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use aoc::crypt;
+use aoc::inputs::DATA_DIR;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+
+pub fn command() -> Command {
+    let paths = Arg::new("paths")
+        .num_args(0..)
+        .value_parser(clap::value_parser!(PathBuf))
+        .help("Files to process, all matching files in data/ by default");
+    let keep = Arg::new("keep")
+        .long("keep")
+        .action(ArgAction::SetTrue)
+        .help("Keep the source files");
+
+    Command::new("data")
+        .about("Manage puzzle inputs")
+        .subcommand_required(true)
+        .subcommand(Command::new("keygen").about("Generate a new key file"))
+        .subcommand(
+            Command::new("encrypt")
+                .about("Encrypt plain text inputs")
+                .arg(paths.clone())
+                .arg(keep.clone()),
+        )
+        .subcommand(
+            Command::new("decrypt")
+                .about("Decrypt encrypted inputs")
+                .arg(paths)
+                .arg(keep),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("keygen", _)) => keygen(),
+        Some(("encrypt", m)) => {
+            let paths = paths_or_default(m, |p| p.extension().is_some_and(|e| e == "txt"))?;
+            encrypt(&paths, m.get_flag("keep"))
+        }
+        Some(("decrypt", m)) => {
+            let paths = paths_or_default(m, crypt::is_encrypted)?;
+            decrypt(&paths, m.get_flag("keep"))
+        }
+        _ => unreachable!("subcommand is required"),
+    }
+}
+
+fn keygen() -> Result<()> {
+    let path = crypt::key_file_path();
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    fs::write(&path, crypt::generate_key())?;
+    println!("Key written to {}", path.display());
+    Ok(())
+}
+
+fn encrypt(paths: &[PathBuf], keep: bool) -> Result<()> {
+    let key = crypt::load_key()?;
+    for path in paths {
+        let plaintext = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        let target = crypt::encrypted_path(path);
+        fs::write(&target, crypt::encrypt(&key, &plaintext)?)?;
+        if !keep {
+            fs::remove_file(path)?;
+        }
+        println!("{} -> {}", path.display(), target.display());
+    }
+    Ok(())
+}
+
+fn decrypt(paths: &[PathBuf], keep: bool) -> Result<()> {
+    let key = crypt::load_key()?;
+    for path in paths {
+        if !crypt::is_encrypted(path) {
+            bail!("{} is not an encrypted file", path.display());
+        }
+        let ciphertext = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        let target = crypt::decrypted_path(path);
+        let plaintext = crypt::decrypt(&key, &ciphertext)
+            .with_context(|| format!("decrypting {}", path.display()))?;
+        fs::write(&target, plaintext)?;
+        if !keep {
+            fs::remove_file(path)?;
+        }
+        println!("{} -> {}", path.display(), target.display());
+    }
+    Ok(())
+}
+
+fn paths_or_default(matches: &ArgMatches, filter: fn(&Path) -> bool) -> Result<Vec<PathBuf>> {
+    if let Some(paths) = matches.get_many::<PathBuf>("paths") {
+        return Ok(paths.cloned().collect());
+    }
+
+    let mut paths = vec![];
+    collect_files(Path::new(DATA_DIR), filter, &mut paths)?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn collect_files(dir: &Path, filter: fn(&Path) -> bool, paths: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, filter, paths)?;
+        } else if filter(&path) {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,620 @@
+use std::fmt::{Display, Formatter};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use aoc::sim::Simulation;
+use clap::{value_parser, Arg, ArgMatches, Command};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use regex::Regex;
+
+use crate::cli;
+use crate::sim;
+
+const HELP: &str = "\
+n [N]        step forward, N steps or 1
+p [N]        step backward, N steps or 1
+g N          go to step N
+c            continue until a breakpoint or the end
+b COND       break when COND holds, like `y >= 150` or `step == 20`
+d            delete all breakpoints
+q            quit
+An empty line repeats the last command.";
+
+const KEYS: &str = "\
+n, →, space  step forward, as many steps as the number typed before or 1
+p, ←         step backward, as many steps as the number typed before or 1
+g            go to a step
+b            break on a condition
+c            continue until a breakpoint or the end
+d            delete all breakpoints
+q, esc       quit";
+
+const KEYS_LINE: &str = "n/→ next  p/← prev  g go to  b break  c continue  d delete  q quit";
+
+/// Lines under the drawing, for the values, the breakpoints, a message or
+/// prompt and the keys.
+const STATUS_LINES: usize = 4;
+
+pub fn command() -> Command {
+    Command::new("debug")
+        .about("Step through the simulation of a part interactively")
+        .long_about(format!(
+            "Step through the simulation of a part interactively, forward and backward, \
+             drawing every step with what moves highlighted. On a terminal it takes the \
+             whole screen, scrolls to follow what moves and reads keys:\n\n{KEYS}\n\n\
+             Otherwise commands are read from stdin, one per line:\n\n{HELP}\n\n\
+             Breakpoints compare the values shown under the drawing, or the step number, \
+             with a number."
+        ))
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().required(true))
+        .arg(
+            Arg::new("part")
+                .required(true)
+                .value_parser(value_parser!(u8).range(1..)),
+        )
+        .args(sim::input_args())
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let debugger = Debugger::new(sim::load_simulation(matches)?);
+    if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        return Screen::new(debugger).run();
+    }
+    let terminal = io::stdout().is_terminal();
+    run_commands(
+        debugger,
+        io::stdin().lock(),
+        &mut io::stdout().lock(),
+        terminal,
+    )
+}
+
+/// Draws the simulation, then reads and runs a command, until the input ends.
+fn run_commands(
+    mut debugger: Debugger,
+    mut input: impl BufRead,
+    out: &mut impl Write,
+    terminal: bool,
+) -> Result<()> {
+    let mut last = "n".to_string();
+    let mut message = String::new();
+    let mut line = String::new();
+    loop {
+        write!(out, "{}", debugger.draw(terminal))?;
+        if !message.is_empty() {
+            writeln!(out, "{message}")?;
+        }
+        out.flush()?;
+
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let command = match line.trim() {
+            "" => last.clone(),
+            command => command.to_string(),
+        };
+        message = match debugger.execute(&command) {
+            Ok(Action::Quit) => return Ok(()),
+            Ok(Action::Show(message)) => message,
+            Err(e) => format!("Error: {e}"),
+        };
+        last = command;
+    }
+}
+
+enum Action {
+    Show(String),
+    Quit,
+}
+
+/// Tests a value of the simulation, or the step number, against a number.
+#[derive(Debug, PartialEq)]
+struct Condition {
+    name: String,
+    operator: String,
+    value: i64,
+}
+
+impl FromStr for Condition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let pattern = Regex::new(r"^\s*(\w+)\s*(==|!=|<=|>=|<|>)\s*(-?\d+)\s*$").unwrap();
+        let Some(captures) = pattern.captures(s) else {
+            bail!("malformed condition {s:?}, expected something like `y >= 150`");
+        };
+        Ok(Self {
+            name: captures[1].to_string(),
+            operator: captures[2].to_string(),
+            value: captures[3].parse()?,
+        })
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.name, self.operator, self.value)
+    }
+}
+
+impl Condition {
+    /// Doesn't hold when the simulation has no such value at the moment.
+    fn holds(&self, values: &[(&str, i64)]) -> bool {
+        let Some(&(_, actual)) = values.iter().find(|&&(name, _)| name == self.name) else {
+            return false;
+        };
+        match self.operator.as_str() {
+            "==" => actual == self.value,
+            "!=" => actual != self.value,
+            "<" => actual < self.value,
+            "<=" => actual <= self.value,
+            ">" => actual > self.value,
+            ">=" => actual >= self.value,
+            _ => unreachable!("matched by the pattern"),
+        }
+    }
+}
+
+/// Keeps the state of every step it went through, so it can go back to them.
+struct Debugger {
+    simulation: Box<dyn Simulation>,
+    /// The state after each step, from the start.
+    states: Vec<String>,
+    step: usize,
+    breakpoints: Vec<Condition>,
+}
+
+impl Debugger {
+    fn new(simulation: Box<dyn Simulation>) -> Self {
+        Self {
+            states: vec![simulation.state()],
+            simulation,
+            step: 0,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    fn values(&self) -> Vec<(&'static str, i64)> {
+        let mut values = vec![("step", self.step as i64)];
+        values.extend(self.simulation.values());
+        values
+    }
+
+    /// Returns false at the end of the simulation.
+    fn forward(&mut self) -> Result<bool> {
+        if let Some(state) = self.states.get(self.step + 1) {
+            self.simulation.restore(state)?;
+        } else if self.simulation.done() {
+            return Ok(false);
+        } else {
+            self.simulation.step();
+            self.states.push(self.simulation.state());
+        }
+        self.step += 1;
+        Ok(true)
+    }
+
+    fn go_to(&mut self, step: usize) -> Result<()> {
+        if step < self.states.len() {
+            self.simulation.restore(&self.states[step])?;
+            self.step = step;
+            return Ok(());
+        }
+        self.go_to(self.states.len() - 1)?;
+        while self.step < step {
+            if !self.forward()? {
+                bail!("the simulation ends at step {}", self.step);
+            }
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, command: &str) -> Result<Action> {
+        let (name, argument) = match command.split_once(' ') {
+            Some((name, argument)) => (name, Some(argument.trim())),
+            None => (command, None),
+        };
+        let count = || -> Result<usize> {
+            match argument {
+                Some(count) => count
+                    .parse()
+                    .map_err(|_| anyhow!("{count:?} is not a number")),
+                None => Ok(1),
+            }
+        };
+
+        let message = match name {
+            "n" | "next" => {
+                let target = self.step + count()?;
+                while self.step < target && self.forward()? {}
+                String::new()
+            }
+            "p" | "prev" => {
+                self.go_to(self.step.saturating_sub(count()?))?;
+                String::new()
+            }
+            "g" | "goto" => {
+                let Some(step) = argument else {
+                    bail!("missing step");
+                };
+                let step = step
+                    .parse()
+                    .map_err(|_| anyhow!("{step:?} is not a step"))?;
+                self.go_to(step)?;
+                String::new()
+            }
+            "c" | "continue" => self.continue_to_breakpoint()?,
+            "b" | "break" => {
+                let condition: Condition = argument.unwrap_or_default().parse()?;
+                let message = format!("Breakpoint {condition}");
+                self.breakpoints.push(condition);
+                message
+            }
+            "d" | "delete" => {
+                self.breakpoints.clear();
+                "Breakpoints deleted".to_string()
+            }
+            "h" | "help" => HELP.to_string(),
+            "q" | "quit" => return Ok(Action::Quit),
+            _ => bail!("unknown command {name:?}, `h` lists them"),
+        };
+        Ok(Action::Show(message))
+    }
+
+    /// Steps at least once, then until a breakpoint holds.
+    fn continue_to_breakpoint(&mut self) -> Result<String> {
+        while self.forward()? {
+            let values = self.values();
+            if let Some(condition) = self.breakpoints.iter().find(|c| c.holds(&values)) {
+                return Ok(format!("Breakpoint {condition} hit"));
+            }
+        }
+        Ok("The simulation is done".to_string())
+    }
+
+    /// The step and the values, and whether the simulation is done.
+    fn status(&self) -> String {
+        let values: Vec<String> = self
+            .values()
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        let mut status = values.join(" ");
+        if self.simulation.done() {
+            status.push_str(" (done)");
+        }
+        status
+    }
+
+    /// Empty without breakpoints.
+    fn breakpoint_list(&self) -> String {
+        if self.breakpoints.is_empty() {
+            return String::new();
+        }
+        let breakpoints: Vec<String> = self.breakpoints.iter().map(|c| c.to_string()).collect();
+        format!("breakpoints: {}", breakpoints.join(", "))
+    }
+
+    /// Draws the simulation, the step and the values. The moving parts are
+    /// shown in reverse video on a terminal, which also gets cleared first.
+    fn draw(&self, terminal: bool) -> String {
+        let mut screen = String::new();
+        if terminal {
+            screen.push_str("\x1b[2J\x1b[H");
+        }
+        let highlighted = self.simulation.highlighted();
+        for (row, line) in self.simulation.render().lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                match terminal && highlighted.contains(&(row, col)) {
+                    true => screen.push_str(&format!("\x1b[7m{c}\x1b[0m")),
+                    false => screen.push(c),
+                }
+            }
+            screen.push('\n');
+        }
+        screen.push_str(&self.status());
+        screen.push('\n');
+        if !self.breakpoints.is_empty() {
+            screen.push_str(&self.breakpoint_list());
+            screen.push('\n');
+        }
+        screen
+    }
+}
+
+/// Puts the terminal in raw mode on the alternate screen, and back when dropped.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let raw = Self;
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        Ok(raw)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// A line typed under the drawing, run with `command` once entered.
+struct Prompt {
+    label: &'static str,
+    command: &'static str,
+    input: String,
+}
+
+/// The full screen interface, driven by keys.
+struct Screen {
+    debugger: Debugger,
+    /// Digits typed before stepping.
+    count: String,
+    prompt: Option<Prompt>,
+    message: String,
+    /// Row and column of the drawing at the top left corner of the screen.
+    scroll: (usize, usize),
+}
+
+impl Screen {
+    fn new(debugger: Debugger) -> Self {
+        Self {
+            debugger,
+            count: String::new(),
+            prompt: None,
+            message: String::new(),
+            scroll: (0, 0),
+        }
+    }
+
+    fn run(mut self) -> Result<()> {
+        let _raw = RawTerminal::enter()?;
+        let mut out = io::stdout().lock();
+        loop {
+            let (width, height) = terminal::size()?;
+            self.draw(&mut out, width.into(), height.into())?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !self.on_key(key) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Returns false to quit.
+    fn on_key(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return false;
+        }
+        if let Some(prompt) = &mut self.prompt {
+            match key.code {
+                KeyCode::Enter => {
+                    let command = format!("{} {}", prompt.command, prompt.input);
+                    self.prompt = None;
+                    return self.execute(&command);
+                }
+                KeyCode::Esc => self.prompt = None,
+                KeyCode::Backspace => _ = prompt.input.pop(),
+                KeyCode::Char(c) => prompt.input.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        let count = std::mem::take(&mut self.count);
+        let steps = if count.is_empty() { "1" } else { &count };
+        let (label, command) = match key.code {
+            KeyCode::Char(digit @ '0'..='9') => {
+                self.count = count + &digit.to_string();
+                return true;
+            }
+            KeyCode::Char('n' | ' ') | KeyCode::Right => {
+                return self.execute(&format!("n {steps}"))
+            }
+            KeyCode::Char('p') | KeyCode::Left => return self.execute(&format!("p {steps}")),
+            KeyCode::Char('c') => return self.execute("c"),
+            KeyCode::Char('d') => return self.execute("d"),
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('g') => ("Go to step", "g"),
+            KeyCode::Char('b') => ("Break when", "b"),
+            _ => return true,
+        };
+        self.prompt = Some(Prompt {
+            label,
+            command,
+            input: String::new(),
+        });
+        true
+    }
+
+    /// Returns false to quit.
+    fn execute(&mut self, command: &str) -> bool {
+        self.message = match self.debugger.execute(command) {
+            Ok(Action::Quit) => return false,
+            Ok(Action::Show(message)) => message,
+            Err(e) => format!("Error: {e}"),
+        };
+        true
+    }
+
+    fn draw(&mut self, out: &mut impl Write, width: usize, height: usize) -> io::Result<()> {
+        let simulation = &self.debugger.simulation;
+        let drawing = simulation.render();
+        let highlighted = simulation.highlighted();
+        let rows = height.saturating_sub(STATUS_LINES);
+        if let Some(&(row, col)) = highlighted.first() {
+            self.scroll = (
+                follow(self.scroll.0, row, rows),
+                follow(self.scroll.1, col, width),
+            );
+        }
+
+        let mut lines = drawing.lines().skip(self.scroll.0);
+        for y in 0..rows {
+            queue!(out, cursor::MoveTo(0, y as u16))?;
+            let row = self.scroll.0 + y;
+            let line = lines.next().unwrap_or_default();
+            for (col, c) in line.chars().enumerate().skip(self.scroll.1).take(width) {
+                match highlighted.contains(&(row, col)) {
+                    true => queue!(
+                        out,
+                        SetAttribute(Attribute::Reverse),
+                        Print(c),
+                        SetAttribute(Attribute::Reset)
+                    )?,
+                    false => queue!(out, Print(c))?,
+                }
+            }
+            queue!(out, Clear(ClearType::UntilNewLine))?;
+        }
+
+        let prompt = match &self.prompt {
+            Some(prompt) => format!("{}: {}", prompt.label, prompt.input),
+            None => self.message.clone(),
+        };
+        let status = [
+            self.debugger.status(),
+            self.debugger.breakpoint_list(),
+            prompt,
+            KEYS_LINE.to_string(),
+        ];
+        for (y, line) in status.iter().enumerate() {
+            let line: String = line.chars().take(width).collect();
+            queue!(
+                out,
+                cursor::MoveTo(0, (rows + y) as u16),
+                Print(line),
+                Clear(ClearType::UntilNewLine)
+            )?;
+        }
+        out.flush()
+    }
+}
+
+/// Returns where a view of `size` starts after scrolling to show `position`,
+/// centering it when it was out of view.
+fn follow(start: usize, position: usize, size: usize) -> usize {
+    match (start..start + size).contains(&position) {
+        true => start,
+        false => position.saturating_sub(size / 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use crossterm::event::{KeyCode, KeyEvent};
+    use year2022::day9;
+
+    use super::{follow, run_commands, Condition, Debugger, Screen};
+
+    const MOTIONS: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n";
+
+    #[test]
+    fn test_condition() -> Result<()> {
+        let condition: Condition = "y>=150".parse()?;
+        assert_eq!(condition.to_string(), "y >= 150");
+        assert!(condition.holds(&[("x", 3), ("y", 150)]));
+        assert!(!condition.holds(&[("y", 149)]));
+        assert!(!condition.holds(&[("x", 200)]));
+        assert!("step != -1".parse::<Condition>()?.holds(&[("step", 0)]));
+        assert!("y => 150".parse::<Condition>().is_err());
+        assert!("".parse::<Condition>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stepping() -> Result<()> {
+        let mut debugger = Debugger::new(day9::sim::load(MOTIONS, 1)?);
+        debugger.execute("n 5")?;
+        assert_eq!(debugger.simulation.state(), "1 1 4,1 3,0");
+        debugger.execute("p 2")?;
+        assert_eq!(debugger.step, 3);
+        assert_eq!(debugger.simulation.state(), "0 3 3,0 2,0");
+        debugger.execute("g 8")?;
+        assert_eq!(debugger.simulation.state(), "2 0 4,4 4,3");
+        assert!(debugger.execute("g 25").is_err());
+        assert_eq!(debugger.step, 24);
+
+        debugger.execute("g 0")?;
+        debugger.execute("b tail_y == 4")?;
+        debugger.execute("c")?;
+        assert_eq!(debugger.step, 10);
+        debugger.execute("c")?;
+        assert_eq!(debugger.step, 11);
+        debugger.execute("d")?;
+        debugger.execute("c")?;
+        assert_eq!(debugger.step, 24);
+        assert!(debugger.execute("x").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_draw_before_reading() -> Result<()> {
+        let debugger = Debugger::new(day9::sim::load(MOTIONS, 1)?);
+        let mut out = Vec::new();
+        run_commands(debugger, "n\n".as_bytes(), &mut out, false)?;
+        let out = String::from_utf8(out)?;
+        let screens: Vec<&str> = out.split("step=").skip(1).collect();
+        assert_eq!(screens.len(), 2);
+        assert!(screens[0].starts_with("0 "));
+        assert!(screens[1].starts_with("1 "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_keys() -> Result<()> {
+        let mut screen = Screen::new(Debugger::new(day9::sim::load(MOTIONS, 1)?));
+        let mut press =
+            |keys: &[KeyCode]| keys.iter().all(|&key| screen.on_key(KeyEvent::from(key)));
+        assert!(press(&[
+            KeyCode::Char('1'),
+            KeyCode::Char('2'),
+            KeyCode::Right
+        ]));
+        assert!(press(&[
+            KeyCode::Char('p'),
+            KeyCode::Char('3'),
+            KeyCode::Left
+        ]));
+        assert!(press(&[
+            KeyCode::Char('g'),
+            KeyCode::Char('5'),
+            KeyCode::Enter
+        ]));
+        assert!(press(&[
+            KeyCode::Char('g'),
+            KeyCode::Char('9'),
+            KeyCode::Esc
+        ]));
+        let keys: Vec<KeyCode> = "btail_y == 4".chars().map(KeyCode::Char).collect();
+        assert!(press(&keys));
+        assert!(press(&[
+            KeyCode::Backspace,
+            KeyCode::Char('4'),
+            KeyCode::Enter
+        ]));
+        assert!(press(&[KeyCode::Char('c')]));
+        assert!(!press(&[KeyCode::Char('q')]));
+        assert_eq!(screen.debugger.step, 10);
+        assert_eq!(screen.message, "Breakpoint tail_y == 4 hit");
+        Ok(())
+    }
+
+    #[test]
+    fn test_follow() {
+        assert_eq!(follow(0, 5, 10), 0);
+        assert_eq!(follow(0, 10, 10), 5);
+        assert_eq!(follow(20, 3, 10), 0);
+        assert_eq!(follow(20, 29, 10), 20);
+    }
+}
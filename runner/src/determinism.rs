@@ -0,0 +1,116 @@
+use std::panic::{self, PanicHookInfo};
+use std::thread;
+
+use anyhow::{bail, Result};
+use aoc::determinism::distinct_outcomes;
+use aoc::random::Rng;
+use clap::{value_parser, Arg, ArgMatches, Command};
+
+use crate::cli;
+
+pub fn command() -> Command {
+    Command::new("check-determinism")
+        .about("Check that solutions give the same answers when run several times")
+        .long_about(
+            "Check that solutions give the same answers when run several times. Each part \
+             runs on the stored inputs of the day and on a generated input, once on the \
+             current thread and in parallel on other threads, so hash maps and sets get \
+             different hasher seeds on every run. Parts whose answers, errors or panics \
+             differ between runs are reported. The allocation and progress counters are \
+             global to the process, so the parallel runs share them and parts that answer \
+             with them differ between runs.",
+        )
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().help("Check only this day, all implemented days by default"))
+        .arg(cli::label_arg())
+        .arg(cli::all_variants_arg())
+        .arg(
+            Arg::new("runs")
+                .long("runs")
+                .short('n')
+                .value_parser(value_parser!(u32).range(2..))
+                .default_value("8")
+                .help("Number of runs of each part on each input"),
+        )
+        .arg(
+            Arg::new("size")
+                .long("size")
+                .short('s')
+                .value_parser(value_parser!(usize))
+                .default_value("50")
+                .help("Size of the generated input"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let runs = *matches.get_one::<u32>("runs").expect("has default") as usize;
+    let size = *matches.get_one::<usize>("size").expect("has default");
+    let all_variants = matches.get_flag("all-variants");
+
+    // Panics are outcomes like any other, they are reported below
+    let _silent = SilentPanics::new();
+
+    let (mut checked, mut nondeterministic) = (0, 0);
+    for solution in cli::selected_solutions(matches)? {
+        let mut inputs = Vec::new();
+        for input in cli::selected_inputs(solution.day, matches)? {
+            inputs.push((input.label.clone(), input.read()?));
+        }
+        if let Some(generator) = solution.generator {
+            inputs.push(("generated".to_string(), generator(size, &mut Rng::new(0))));
+        }
+
+        for (label, data) in &inputs {
+            for part in 1..=solution.parts.len() as u8 {
+                for (name, solve) in cli::part_variants(solution, part, all_variants) {
+                    let mut prefix = format!("Day {} [{label}] part {part}", solution.day);
+                    if all_variants {
+                        prefix.push_str(&format!(" [{name}]"));
+                    }
+                    checked += 1;
+
+                    let outcomes = distinct_outcomes(solve, data, runs);
+                    if outcomes.len() == 1 {
+                        println!("{prefix}: {runs} runs agree");
+                        continue;
+                    }
+                    nondeterministic += 1;
+                    println!(
+                        "{prefix}: NONDETERMINISTIC, {} different outcomes in {runs} runs",
+                        outcomes.len()
+                    );
+                    for (outcome, count) in outcomes {
+                        println!("  {count} runs: {outcome}");
+                    }
+                }
+            }
+        }
+    }
+    match (checked, nondeterministic) {
+        (0, _) => bail!("no inputs to check"),
+        (_, 0) => Ok(()),
+        _ => bail!("{nondeterministic} parts are nondeterministic"),
+    }
+}
+
+type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send>;
+
+/// Keeps panics from being printed until dropped, on errors too.
+struct SilentPanics(Option<PanicHook>);
+
+impl SilentPanics {
+    fn new() -> Self {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        SilentPanics(Some(hook))
+    }
+}
+
+impl Drop for SilentPanics {
+    fn drop(&mut self) {
+        // The hook can't be changed while the thread panics
+        if let (Some(hook), false) = (self.0.take(), thread::panicking()) {
+            panic::set_hook(hook);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use aoc::difftest::find_mismatch;
+use clap::{value_parser, Arg, ArgMatches, Command};
+
+use crate::cli;
+
+pub const MISMATCH_DIR: &str = ".aoc-cache/difftest";
+
+pub fn command() -> Command {
+    Command::new("difftest")
+        .about("Compare optimised parts with their reference implementations on generated inputs")
+        .long_about(
+            "Compare optimised parts with their reference implementations on generated inputs. \
+             Input i is generated from seed --seed + i. The first input a part and its \
+             reference disagree on is saved to .aoc-cache/difftest.",
+        )
+        .arg(cli::year_arg())
+        .arg(cli::day_arg())
+        .arg(
+            Arg::new("cases")
+                .long("cases")
+                .short('n')
+                .value_parser(value_parser!(u64))
+                .default_value("100")
+                .help("Number of inputs to generate"),
+        )
+        .arg(
+            Arg::new("size")
+                .long("size")
+                .short('s')
+                .value_parser(value_parser!(usize))
+                .default_value("20")
+                .help("Size of the generated inputs"),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .value_parser(value_parser!(u64))
+                .default_value("0")
+                .help("Seed of the first input"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let year = *matches.get_one::<u16>("year").expect("required");
+    let cases = *matches.get_one::<u64>("cases").expect("has default");
+    let size = *matches.get_one::<usize>("size").expect("has default");
+    let seed = *matches.get_one::<u64>("seed").expect("has default");
+
+    let mut tested = 0;
+    let mut mismatches = 0;
+    for solution in cli::selected_solutions(matches)? {
+        for part in 1..=solution.parts.len() as u8 {
+            if solution.generator.is_none() || solution.reference(part).is_none() {
+                continue;
+            }
+            tested += 1;
+
+            let label = format!("Day {} part {part}", solution.day);
+            let Some(mismatch) = find_mismatch(solution, part, size, seed..seed + cases) else {
+                println!("{label}: {cases} inputs agree");
+                continue;
+            };
+            mismatches += 1;
+
+            let path = PathBuf::from(MISMATCH_DIR).join(format!(
+                "{year}-day{}-part{part}-seed{}.txt",
+                solution.day, mismatch.seed
+            ));
+            fs::create_dir_all(MISMATCH_DIR)?;
+            fs::write(&path, &mismatch.input)
+                .with_context(|| format!("writing {}", path.display()))?;
+            println!(
+                "{label}: disagree on seed {} ({})",
+                mismatch.seed,
+                path.display()
+            );
+            println!("  answer:    {}", mismatch.answer);
+            println!("  reference: {}", mismatch.reference);
+        }
+    }
+
+    match (tested, mismatches) {
+        (0, _) => bail!("no part has a reference implementation"),
+        (_, 0) => Ok(()),
+        _ => bail!("{mismatches} parts disagree with their reference"),
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
+
+/// Puzzles unlock at midnight in UTC-5.
+pub const UNLOCK_OFFSET_HOURS: i32 = -5;
+pub const DAYS: u8 = 25;
+
+pub fn unlock_timezone() -> FixedOffset {
+    FixedOffset::east_opt(UNLOCK_OFFSET_HOURS * 3600).expect("valid offset")
+}
+
+pub fn unlock_time(year: u16, day: u8) -> DateTime<Utc> {
+    NaiveDate::from_ymd_opt(i32::from(year), 12, u32::from(day))
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .and_then(|t| t.and_local_timezone(unlock_timezone()).single())
+        .expect("valid event day")
+        .with_timezone(&Utc)
+}
+
+/// Returns the year and day of the latest unlocked puzzle if an event is running.
+pub fn event_day(now: DateTime<Utc>) -> Option<(u16, u8)> {
+    let local = now.with_timezone(&unlock_timezone());
+    match (local.month(), local.day()) {
+        (12, day) if day <= u32::from(DAYS) => Some((local.year() as u16, day as u8)),
+        _ => None,
+    }
+}
+
+/// Returns the year and day of the next puzzle to unlock and when it happens.
+pub fn next_unlock(now: DateTime<Utc>) -> (u16, u8, DateTime<Utc>) {
+    let local = now.with_timezone(&unlock_timezone());
+    let year = local.year() as u16;
+    let (year, day) = match event_day(now) {
+        Some((year, day)) if day < DAYS => (year, day + 1),
+        Some(_) => (year + 1, 1),
+        None if local.month() == 12 => (year + 1, 1),
+        None => (year, 1),
+    };
+    (year, day, unlock_time(year, day))
+}
+
+/// The year of the running event, or of the last one outside December.
+pub fn latest_event_year(now: DateTime<Utc>) -> u16 {
+    let local = now.with_timezone(&unlock_timezone());
+    match local.month() {
+        12 => local.year() as u16,
+        _ => local.year() as u16 - 1,
+    }
+}
+
+/// Formats a duration in seconds as `HH:MM:SS`, hours can go past 24.
+pub fn format_seconds(seconds: i64) -> String {
+    let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    format!("{h:02}:{m:02}:{s:02}")
+}
+
+/// Formats a countdown as `HH:MM:SS`, prefixed with the number of days when longer.
+pub fn format_countdown(seconds: i64) -> String {
+    match seconds / 86_400 {
+        0 => format_seconds(seconds),
+        days => format!("{days}d {}", format_seconds(seconds % 86_400)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(
+            unlock_time(2022, 1).to_rfc3339(),
+            "2022-12-01T05:00:00+00:00"
+        );
+        assert_eq!(unlock_time(2022, 25).timestamp(), 1671944400);
+    }
+
+    #[test]
+    fn test_event_day() {
+        // 2022-12-12 04:59:59 UTC is still December 11 in UTC-5
+        let now = Utc.with_ymd_and_hms(2022, 12, 12, 4, 59, 59).unwrap();
+        assert_eq!(event_day(now), Some((2022, 11)));
+        assert_eq!(next_unlock(now), (2022, 12, unlock_time(2022, 12)));
+
+        let now = Utc.with_ymd_and_hms(2022, 12, 26, 5, 0, 0).unwrap();
+        assert_eq!(event_day(now), None);
+        assert_eq!(next_unlock(now).0, 2023);
+        assert_eq!(latest_event_year(now), 2022);
+
+        let now = Utc.with_ymd_and_hms(2023, 10, 19, 12, 0, 0).unwrap();
+        assert_eq!(next_unlock(now), (2023, 1, unlock_time(2023, 1)));
+        assert_eq!(latest_event_year(now), 2022);
+    }
+
+    #[test]
+    fn test_format_seconds() {
+        assert_eq!(format_seconds(754), "00:12:34");
+        assert_eq!(format_seconds(26 * 3600 + 5), "26:00:05");
+        assert_eq!(format_countdown(26 * 3600 + 5), "1d 02:00:05");
+    }
+}
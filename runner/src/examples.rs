@@ -0,0 +1,130 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use aoc::inputs::{Answers, Input};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+
+use crate::page::PuzzlePage;
+
+pub fn command() -> Command {
+    let import = Command::new("import")
+        .about("Extract example inputs and answers from a saved puzzle page")
+        .arg(
+            Arg::new("page")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("year")
+                .long("year")
+                .value_parser(value_parser!(u16))
+                .help("Override the year found in the page"),
+        )
+        .arg(
+            Arg::new("day")
+                .long("day")
+                .value_parser(value_parser!(u8).range(1..=25))
+                .help("Override the day found in the page"),
+        )
+        .arg(
+            Arg::new("block")
+                .long("block")
+                .short('b')
+                .action(ArgAction::Append)
+                .value_parser(value_parser!(u32).range(1..))
+                .help("Code blocks to save, only the first one by default"),
+        )
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .short('f')
+                .action(ArgAction::SetTrue)
+                .help("Overwrite existing fixtures"),
+        );
+
+    Command::new("examples")
+        .about("Manage example inputs")
+        .subcommand_required(true)
+        .subcommand(import)
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("import", m)) => import(m),
+        _ => unreachable!("subcommand is required"),
+    }
+}
+
+fn import(matches: &ArgMatches) -> Result<()> {
+    let path = matches.get_one::<PathBuf>("page").expect("required");
+    let html = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let page = PuzzlePage::parse(&html);
+
+    let year = match matches.get_one::<u16>("year").copied().or(page.year) {
+        Some(year) => year,
+        None => bail!("year not found in the page, pass it with --year"),
+    };
+    let day = match matches.get_one::<u8>("day").copied().or(page.day) {
+        Some(day) => day,
+        None => bail!("day not found in the page, pass it with --day"),
+    };
+
+    if page.cache(year, day, &html)? {
+        println!("Page cached, read it with `aoc read {year} {day}`");
+    }
+
+    let examples: Vec<&String> = page.examples().collect();
+    if examples.is_empty() {
+        bail!("no code blocks found in {}", path.display());
+    }
+    for (i, example) in examples.iter().enumerate() {
+        let first_line = example.lines().next().unwrap_or_default();
+        println!("Block {}: {first_line}", i + 1);
+    }
+
+    let blocks: Vec<usize> = match matches.get_many::<u32>("block") {
+        Some(blocks) => blocks.map(|&b| b as usize).collect(),
+        None => vec![1],
+    };
+    let force = matches.get_flag("force");
+
+    for (index, &block) in (1..).zip(&blocks) {
+        let example = match examples.get(block - 1) {
+            Some(example) => example,
+            None => bail!("the page has only {} code blocks", examples.len()),
+        };
+        let input = Input::example(year, day, index);
+        write_new(&input.path, example, force)?;
+        println!("Block {block} saved to {}", input.path.display());
+    }
+
+    let answers: Answers = (1..)
+        .zip(&page.parts)
+        .filter_map(|(part, p)| p.answer.clone().map(|a| (part, a)))
+        .collect();
+    let input = Input::example(year, day, 1);
+    if answers.is_empty() {
+        println!("No expected answers found");
+    } else if input.answers_path().exists() && !force {
+        println!("{} already exists", input.answers_path().display());
+    } else {
+        input.write_answers(&answers)?;
+        println!("Answers saved to {}", input.answers_path().display());
+    }
+    Ok(())
+}
+
+fn write_new(path: &Path, contents: &str, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        bail!(
+            "{} already exists, use --force to overwrite it",
+            path.display()
+        );
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
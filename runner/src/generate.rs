@@ -0,0 +1,79 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use aoc::random::Rng;
+use clap::{value_parser, Arg, ArgMatches, Command, ValueHint};
+
+use crate::cli;
+
+const DEFAULT_SIZE: usize = 100;
+
+pub fn command() -> Command {
+    Command::new("gen")
+        .about("Generate a random input for a day")
+        .long_about(
+            "Generate a random input for a day. The same seed and size always give the same \
+             input. Without --seed a random one is used and printed to stderr.",
+        )
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().required(true))
+        .arg(
+            Arg::new("size")
+                .long("size")
+                .short('s')
+                .value_parser(value_parser!(usize))
+                .help("How big the input is, in lines, items or grid cells depending on the day"),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .value_parser(value_parser!(u64))
+                .help("Seed of the random generator"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .short('o')
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath)
+                .help("Write the input to this file instead of stdout"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let solution = cli::selected_solutions(matches)?[0];
+    let Some(generator) = solution.generator else {
+        bail!("day {} has no input generator", solution.day);
+    };
+    let size = matches
+        .get_one::<usize>("size")
+        .copied()
+        .unwrap_or(DEFAULT_SIZE);
+    let seed = match matches.get_one::<u64>("seed") {
+        Some(&seed) => seed,
+        None => {
+            let seed = random_seed();
+            eprintln!("seed {seed}");
+            seed
+        }
+    };
+
+    let input = generator(size, &mut Rng::new(seed));
+    match matches.get_one::<PathBuf>("output") {
+        Some(path) => {
+            fs::write(path, input).with_context(|| format!("writing {}", path.display()))?
+        }
+        None => io::stdout().write_all(input.as_bytes())?,
+    }
+    Ok(())
+}
+
+fn random_seed() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Rng::new(now.as_nanos() as u64 ^ u64::from(std::process::id())).next_u64()
+}
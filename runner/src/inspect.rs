@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use aoc::inspect::Summary;
+use aoc::read_puzzle_data;
+use clap::{value_parser, Arg, ArgMatches, Command, ValueHint};
+
+pub fn command() -> Command {
+    Command::new("inspect")
+        .about("Describe the shape of input files and warn about bad copy-pastes")
+        .long_about(
+            "Describe the shape of input files: lines and blocks, line lengths, chars, \
+             grid size and the range of the numbers in them. Warns about CRLF line endings, \
+             trailing whitespace and a missing final newline.",
+        )
+        .arg(
+            Arg::new("input")
+                .required(true)
+                .num_args(1..)
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let paths: Vec<&PathBuf> = matches.get_many("input").expect("required").collect();
+    for (i, path) in paths.iter().enumerate() {
+        let data = read_puzzle_data(path).with_context(|| format!("reading {}", path.display()))?;
+        if i > 0 {
+            println!();
+        }
+        println!("{}", path.display());
+        for line in Summary::of(&data).to_string().lines() {
+            println!("  {line}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,317 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use serde::{Deserialize, Serialize};
+
+use crate::event::{format_seconds, unlock_time, DAYS};
+
+/// The JSON export of a private leaderboard.
+#[derive(Deserialize)]
+struct Leaderboard {
+    event: String,
+    members: BTreeMap<String, Member>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    stars: u32,
+    completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Deserialize)]
+struct Star {
+    get_star_ts: i64,
+}
+
+#[derive(Serialize)]
+struct Stats {
+    event: String,
+    through_day: u8,
+    members: Vec<MemberStats>,
+}
+
+#[derive(Serialize)]
+struct MemberStats {
+    rank: usize,
+    /// Positive when the member moved up since the previous day.
+    rank_change: Option<i64>,
+    id: u64,
+    name: String,
+    local_score: u32,
+    stars: u32,
+    days: Vec<DayStats>,
+}
+
+#[derive(Serialize)]
+struct DayStats {
+    day: u8,
+    /// Seconds from the unlock to the first star.
+    star1: Option<i64>,
+    /// Seconds from the first star to the second one.
+    star2_delta: Option<i64>,
+}
+
+pub fn command() -> Command {
+    let stats = Command::new("stats")
+        .about("Show stars, solve times and ranks from a private leaderboard export")
+        .arg(
+            Arg::new("file")
+                .required(true)
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .help("Print the statistics as JSON"),
+        );
+
+    Command::new("leaderboard")
+        .about("Private leaderboard tools")
+        .subcommand_required(true)
+        .subcommand(stats)
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("stats", m)) => {
+            let path = m.get_one::<PathBuf>("file").expect("required");
+            let json =
+                fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+            let leaderboard: Leaderboard = serde_json::from_str(&json)
+                .with_context(|| format!("parsing {}", path.display()))?;
+            let stats = compute_stats(&leaderboard)?;
+
+            match m.get_flag("json") {
+                true => println!("{}", serde_json::to_string_pretty(&stats)?),
+                false => print_stats(&stats),
+            }
+            Ok(())
+        }
+        _ => unreachable!("subcommand is required"),
+    }
+}
+
+fn compute_stats(leaderboard: &Leaderboard) -> Result<Stats> {
+    let year: u16 = leaderboard
+        .event
+        .parse()
+        .with_context(|| format!("unexpected event {:?}", leaderboard.event))?;
+    let members: Vec<&Member> = leaderboard.members.values().collect();
+    let days = members.iter().flat_map(|m| m.completion_day_level.keys());
+    if let Some(day) = days.clone().find(|&day| !(1..=DAYS).contains(day)) {
+        bail!("unexpected day {day}, event days go from 1 to {DAYS}");
+    }
+    let through_day = days.max().copied().unwrap_or(0);
+
+    let ranks = ranking(&members, through_day);
+    let previous_ranks = match through_day > 1 {
+        true => Some(ranking(&members, through_day - 1)),
+        false => None,
+    };
+    let scores = local_scores(&members, through_day);
+
+    let mut stats: Vec<MemberStats> = members
+        .iter()
+        .map(|m| {
+            let rank = ranks[&m.id];
+            let rank_change = previous_ranks
+                .as_ref()
+                .map(|previous| previous[&m.id] as i64 - rank as i64);
+            let days = m
+                .completion_day_level
+                .iter()
+                .map(|(&day, stars)| {
+                    let star1 = stars.get(&1).map(|s| s.get_star_ts);
+                    let star2 = stars.get(&2).map(|s| s.get_star_ts);
+                    DayStats {
+                        day,
+                        star1: star1.map(|ts| ts - unlock_time(year, day).timestamp()),
+                        star2_delta: star1.zip(star2).map(|(s1, s2)| s2 - s1),
+                    }
+                })
+                .collect();
+            MemberStats {
+                rank,
+                rank_change,
+                id: m.id,
+                name: display_name(m),
+                local_score: scores[&m.id],
+                stars: m.stars,
+                days,
+            }
+        })
+        .collect();
+    stats.sort_by_key(|s| s.rank);
+
+    Ok(Stats {
+        event: leaderboard.event.clone(),
+        through_day,
+        members: stats,
+    })
+}
+
+/// Every star gives as many points as there are members to whoever got it first,
+/// one point less to the second one and so on.
+fn local_scores(members: &[&Member], through_day: u8) -> HashMap<u64, u32> {
+    let mut scores: HashMap<u64, u32> = members.iter().map(|m| (m.id, 0)).collect();
+    for day in 1..=through_day {
+        for star in [1, 2] {
+            let mut finishers: Vec<(i64, u64)> = members
+                .iter()
+                .filter_map(|m| {
+                    let ts = m.completion_day_level.get(&day)?.get(&star)?.get_star_ts;
+                    Some((ts, m.id))
+                })
+                .collect();
+            finishers.sort();
+            for (points, (_, id)) in (1..=members.len() as u32).rev().zip(finishers) {
+                *scores.get_mut(&id).expect("member exists") += points;
+            }
+        }
+    }
+    scores
+}
+
+/// Ranks members by local score, earlier last star wins a tie.
+fn ranking(members: &[&Member], through_day: u8) -> HashMap<u64, usize> {
+    let scores = local_scores(members, through_day);
+    let mut order: Vec<(u32, i64, u64)> = members
+        .iter()
+        .map(|m| {
+            let last_star_ts = m
+                .completion_day_level
+                .range(..=through_day)
+                .flat_map(|(_, stars)| stars.values().map(|s| s.get_star_ts))
+                .max()
+                .unwrap_or(i64::MAX);
+            (scores[&m.id], last_star_ts, m.id)
+        })
+        .collect();
+    order.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+    (1..)
+        .zip(order)
+        .map(|(rank, (_, _, id))| (id, rank))
+        .collect()
+}
+
+fn display_name(member: &Member) -> String {
+    match &member.name {
+        Some(name) => name.clone(),
+        None => format!("(anonymous user #{})", member.id),
+    }
+}
+
+fn print_stats(stats: &Stats) {
+    println!(
+        "Private leaderboard {}, {} members, through day {}\n",
+        stats.event,
+        stats.members.len(),
+        stats.through_day
+    );
+    println!("Rank  Change  Score  Stars  Name");
+    for m in &stats.members {
+        let change = match m.rank_change {
+            None | Some(0) => "-".to_owned(),
+            Some(change) => format!("{change:+}"),
+        };
+        println!(
+            "{:>4}  {change:>6}  {:>5}  {:>5}  {}",
+            m.rank, m.local_score, m.stars, m.name
+        );
+    }
+
+    println!("\nSolve times: star 1 after the unlock, star 2 after star 1");
+    for m in &stats.members {
+        println!("\n{}", m.name);
+        println!("  Day    Star 1    Star 2");
+        for d in &m.days {
+            let star1 = d.star1.map(format_seconds).unwrap_or_default();
+            let star2 = d.star2_delta.map(format_seconds).unwrap_or_default();
+            let line = format!("  {:>3}  {star1:>8}  {star2:>8}", d.day);
+            println!("{}", line.trim_end());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"{
+        "event": "2022",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 5, "global_score": 0,
+                "last_star_ts": 1670000000,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1669871400, "star_index": 1},
+                        "2": {"get_star_ts": 1669871700, "star_index": 2}
+                    },
+                    "2": {"1": {"get_star_ts": 1669960000, "star_index": 5}}
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 3, "local_score": 5, "global_score": 0,
+                "last_star_ts": 1669958000,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1669872000, "star_index": 3}},
+                    "2": {
+                        "1": {"get_star_ts": 1669957600, "star_index": 4},
+                        "2": {"get_star_ts": 1669958000, "star_index": 6}
+                    }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_compute_stats() {
+        let leaderboard: Leaderboard = serde_json::from_str(EXPORT).unwrap();
+        let stats = compute_stats(&leaderboard).unwrap();
+        assert_eq!(stats.through_day, 2);
+
+        let bob = &stats.members[0];
+        assert_eq!(bob.name, "(anonymous user #2)");
+        assert_eq!(
+            (bob.rank, bob.rank_change, bob.local_score),
+            (1, Some(1), 5)
+        );
+
+        let alice = &stats.members[1];
+        assert_eq!(
+            (alice.rank, alice.rank_change, alice.local_score),
+            (2, Some(-1), 5)
+        );
+        assert_eq!(alice.days[0].star1, Some(10 * 60));
+        assert_eq!(alice.days[0].star2_delta, Some(5 * 60));
+        assert_eq!(alice.days[1].star2_delta, None);
+    }
+
+    #[test]
+    fn test_compute_stats_bad_day() {
+        for day in ["0", "26"] {
+            let export = EXPORT.replace(
+                r#""2": {"1": {"get_star_ts""#,
+                &format!(r#""{day}": {{"1": {{"get_star_ts""#),
+            );
+            let leaderboard: Leaderboard = serde_json::from_str(&export).unwrap();
+            let error = compute_stats(&leaderboard).err().unwrap();
+            assert_eq!(
+                error.to_string(),
+                format!("unexpected day {day}, event days go from 1 to 25")
+            );
+        }
+
+        let export = EXPORT.replace(r#""event": "2022""#, r#""event": "2022x""#);
+        let leaderboard: Leaderboard = serde_json::from_str(&export).unwrap();
+        assert!(compute_stats(&leaderboard).is_err());
+    }
+}
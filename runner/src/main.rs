@@ -0,0 +1,97 @@
+use anyhow::Result;
+use clap::Command;
+use clap_complete::CompleteEnv;
+
+mod attempts;
+mod bench;
+mod cli;
+mod completions;
+mod data;
+mod debug;
+mod determinism;
+mod difftest;
+mod event;
+mod examples;
+mod generate;
+mod inspect;
+mod leaderboard;
+mod man;
+mod markdown;
+mod page;
+mod plugin;
+mod progress;
+mod read;
+mod run;
+mod serve;
+mod shrink;
+mod sim;
+mod snapshots;
+mod today;
+mod verify;
+mod watch;
+
+fn command() -> Command {
+    Command::new("aoc")
+        .about("Advent of Code runner")
+        .subcommand_required(true)
+        .arg(plugin::plugin_arg())
+        .subcommand(run::command())
+        .subcommand(verify::command())
+        .subcommand(bench::command())
+        .subcommand(watch::command())
+        .subcommand(serve::command())
+        .subcommand(generate::command())
+        .subcommand(difftest::command())
+        .subcommand(shrink::command())
+        .subcommand(determinism::command())
+        .subcommand(inspect::command())
+        .subcommand(sim::command())
+        .subcommand(debug::command())
+        .subcommand(snapshots::command())
+        .subcommand(read::command())
+        .subcommand(today::command())
+        .subcommand(data::command())
+        .subcommand(examples::command())
+        .subcommand(leaderboard::command())
+        .subcommand(completions::command())
+        .subcommand(man::command())
+}
+
+fn main() -> Result<()> {
+    CompleteEnv::with_factory(command)
+        .var(completions::COMPLETE_ENV)
+        .complete();
+    let matches = command().get_matches();
+
+    match matches.subcommand() {
+        Some(("run", m)) => run::run(m),
+        Some(("verify", m)) => verify::run(m),
+        Some(("bench", m)) => bench::run(m),
+        Some(("watch", m)) => watch::run(m),
+        Some(("serve", m)) => serve::run(m),
+        Some(("gen", m)) => generate::run(m),
+        Some(("difftest", m)) => difftest::run(m),
+        Some(("shrink", m)) => shrink::run(m),
+        Some(("check-determinism", m)) => determinism::run(m),
+        Some(("inspect", m)) => inspect::run(m),
+        Some(("sim", m)) => sim::run(m),
+        Some(("debug", m)) => debug::run(m),
+        Some(("snapshots", m)) => snapshots::run(m),
+        Some(("read", m)) => read::run(m),
+        Some(("today", m)) => today::run(m),
+        Some(("data", m)) => data::run(m),
+        Some(("examples", m)) => examples::run(m),
+        Some(("leaderboard", m)) => leaderboard::run(m),
+        Some(("completions", m)) => completions::run(m),
+        Some(("man", m)) => man::run(m),
+        _ => unreachable!("subcommand is required"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_command() {
+        super::command().debug_assert();
+    }
+}
@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+use std::{fs, io};
+
+use anyhow::{Context, Result};
+use clap::{value_parser, Arg, ArgMatches, Command, ValueHint};
+
+pub fn command() -> Command {
+    Command::new("man")
+        .about("Print the man page of the runner")
+        .arg(
+            Arg::new("out-dir")
+                .long("out-dir")
+                .short('o')
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::DirPath)
+                .help("Write man pages of the runner and every subcommand to this directory"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let command = crate::command();
+    match matches.get_one::<PathBuf>("out-dir") {
+        Some(dir) => {
+            fs::create_dir_all(dir)?;
+            clap_mangen::generate_to(command, dir)
+                .with_context(|| format!("writing man pages to {}", dir.display()))?;
+            println!("Wrote man pages to {}", dir.display());
+        }
+        None => clap_mangen::Man::new(command).render(&mut io::stdout())?,
+    }
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use scraper::{ElementRef, Node};
+
+/// Converts the contents of a puzzle description to Markdown, keeping
+/// headings, paragraphs, lists, code blocks, inline code and emphasis.
+pub fn to_markdown(element: ElementRef) -> String {
+    let mut blocks = Vec::new();
+    for child in element.children() {
+        match child.value() {
+            Node::Element(_) => {
+                let el = ElementRef::wrap(child).expect("element node");
+                blocks.push(block(el));
+            }
+            Node::Text(text) => blocks.push(collapse_whitespace(text)),
+            _ => {}
+        }
+    }
+
+    let blocks: Vec<&str> = blocks
+        .iter()
+        .map(|b| b.trim())
+        .filter(|b| !b.is_empty())
+        .collect();
+    blocks.join("\n\n") + "\n"
+}
+
+fn block(element: ElementRef) -> String {
+    match element.value().name() {
+        "h1" | "h2" | "h3" => format!("## {}", inline(element).trim()),
+        "pre" => {
+            let code: String = element.text().collect();
+            format!("```\n{}\n```", code.trim_end_matches('\n'))
+        }
+        "ul" | "ol" => element
+            .children()
+            .filter_map(ElementRef::wrap)
+            .map(|li| format!("- {}", inline(li).trim()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => inline(element),
+    }
+}
+
+fn inline(element: ElementRef) -> String {
+    let mut s = String::new();
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => s.push_str(&collapse_whitespace(text)),
+            Node::Element(e) => {
+                let el = ElementRef::wrap(child).expect("element node");
+                match e.name() {
+                    "code" => {
+                        let code: String = el.text().collect();
+                        match el.children().filter_map(ElementRef::wrap).next() {
+                            // <code><em>13</em></code> is how answers are highlighted
+                            Some(em) if em.value().name() == "em" && fully_wraps(el, em) => {
+                                s.push_str(&format!("*`{code}`*"))
+                            }
+                            _ => s.push_str(&format!("`{code}`")),
+                        }
+                    }
+                    "em" | "i" => s.push_str(&format!("*{}*", inline(el))),
+                    "strong" | "b" => s.push_str(&format!("**{}**", inline(el))),
+                    "a" => match e.attr("href") {
+                        Some(href) => s.push_str(&format!("[{}]({href})", inline(el))),
+                        None => s.push_str(&inline(el)),
+                    },
+                    "br" => s.push('\n'),
+                    _ => s.push_str(&inline(el)),
+                }
+            }
+            _ => {}
+        }
+    }
+    s
+}
+
+fn fully_wraps(outer: ElementRef, inner: ElementRef) -> bool {
+    outer.text().collect::<String>() == inner.text().collect::<String>()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut s = String::with_capacity(text.len());
+    let mut last_was_space = false;
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            if !last_was_space {
+                s.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            s.push(ch);
+            last_was_space = false;
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::{Html, Selector};
+
+    use super::to_markdown;
+
+    #[test]
+    fn test_to_markdown() {
+        let html = Html::parse_fragment(
+            r#"<article class="day-desc"><h2>--- Day 16: Proboscidea Volcanium ---</h2>
+<p>The <em>sensors</em> have led you to the origin of the
+<a href="/2022/day/15">distress signal</a>.</p>
+<pre><code>Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve <em>BB</em> has flow rate=13; tunnels lead to valves CC, AA
+</code></pre>
+<ul><li>Valve <code>AA</code></li><li>You can release <code><em>1651</em></code>.</li></ul>
+</article>"#,
+        );
+        let article = html
+            .select(&Selector::parse("article").unwrap())
+            .next()
+            .unwrap();
+
+        assert_eq!(
+            to_markdown(article),
+            "## --- Day 16: Proboscidea Volcanium ---\n\n\
+             The *sensors* have led you to the origin of the [distress signal](/2022/day/15).\n\n\
+             ```\n\
+             Valve AA has flow rate=0; tunnels lead to valves DD, II, BB\n\
+             Valve BB has flow rate=13; tunnels lead to valves CC, AA\n\
+             ```\n\n\
+             - Valve `AA`\n\
+             - You can release *`1651`*.\n"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::path::PathBuf;
+use std::{fs, io};
+
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::markdown::to_markdown;
+
+pub const CACHE_DIR: &str = ".aoc-cache/puzzles";
+
+/// A puzzle page saved from the Advent of Code website.
+pub struct PuzzlePage {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub parts: Vec<PartDescription>,
+}
+
+/// One `<article class="day-desc">` of the page. Part 2 is only present
+/// in pages saved after solving part 1.
+pub struct PartDescription {
+    pub markdown: String,
+    pub examples: Vec<String>,
+    pub answer: Option<String>,
+}
+
+impl PuzzlePage {
+    pub fn parse(html: &str) -> Self {
+        let document = Html::parse_document(html);
+        let (year, day) = parse_title(&document);
+        let parts = document
+            .select(&selector("article.day-desc"))
+            .map(PartDescription::from_article)
+            .collect();
+        Self { year, day, parts }
+    }
+
+    pub fn cache_path(year: u16, day: u8) -> PathBuf {
+        PathBuf::from(format!("{CACHE_DIR}/{year}/day{day}.html"))
+    }
+
+    /// Returns `None` if the page has not been cached yet.
+    pub fn load_cached(year: u16, day: u8) -> io::Result<Option<Self>> {
+        match fs::read_to_string(Self::cache_path(year, day)) {
+            Ok(html) => Ok(Some(Self::parse(&html))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Caches the page unless the cached one already describes more parts,
+    /// so a page saved before solving part 1 never replaces a complete one.
+    pub fn cache(&self, year: u16, day: u8, html: &str) -> io::Result<bool> {
+        if let Some(cached) = Self::load_cached(year, day)? {
+            if cached.parts.len() > self.parts.len() {
+                return Ok(false);
+            }
+        }
+
+        let path = Self::cache_path(year, day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, html)?;
+        Ok(true)
+    }
+
+    pub fn examples(&self) -> impl Iterator<Item = &String> {
+        self.parts.iter().flat_map(|p| &p.examples)
+    }
+}
+
+impl PartDescription {
+    fn from_article(article: ElementRef) -> Self {
+        let examples = article
+            .select(&selector("pre > code"))
+            .map(|e| e.text().collect())
+            .collect();
+        // The answer to the example is the last emphasized code in the description
+        let answer = article
+            .select(&selector("code > em, em > code"))
+            .filter(|e| !inside_pre(e))
+            .last()
+            .map(|e| e.text().collect());
+        Self {
+            markdown: to_markdown(article),
+            examples,
+            answer,
+        }
+    }
+}
+
+fn inside_pre(element: &ElementRef) -> bool {
+    element
+        .ancestors()
+        .any(|a| a.value().as_element().is_some_and(|e| e.name() == "pre"))
+}
+
+fn parse_title(document: &Html) -> (Option<u16>, Option<u8>) {
+    let title: String = match document.select(&selector("title")).next() {
+        Some(title) => title.text().collect(),
+        None => return (None, None),
+    };
+    let re = Regex::new(r"Day (\d+) - Advent of Code (\d+)").expect("valid regex");
+    match re.captures(&title) {
+        Some(captures) => (captures[2].parse().ok(), captures[1].parse().ok()),
+        None => (None, None),
+    }
+}
+
+fn selector(s: &str) -> Selector {
+    Selector::parse(s).expect("valid selector")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PuzzlePage;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head><title>Day 13 - Advent of Code 2022</title></head>
+<body><main>
+<article class="day-desc"><h2>--- Day 13: Distress Signal ---</h2>
+<p>For example:</p>
+<pre><code>[1,1,3,1,1]
+[1,1,5,1,1]
+</code></pre>
+<p>In the above example, the sum of these indices is <code><em>13</em></code>.</p>
+<pre><code>[<em>[2]</em>]
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>5340</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Multiply them together: <code>10 * 14 = <em>140</em></code>.</p>
+</article>
+</main></body>
+</html>"#;
+
+    #[test]
+    fn test_parse() {
+        let page = PuzzlePage::parse(PAGE);
+        assert_eq!(page.year, Some(2022));
+        assert_eq!(page.day, Some(13));
+        assert_eq!(page.parts.len(), 2);
+        assert_eq!(
+            page.examples().collect::<Vec<_>>(),
+            ["[1,1,3,1,1]\n[1,1,5,1,1]\n", "[[2]]\n"]
+        );
+        assert_eq!(page.parts[0].answer.as_deref(), Some("13"));
+        assert_eq!(page.parts[1].answer.as_deref(), Some("140"));
+    }
+}
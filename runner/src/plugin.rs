@@ -0,0 +1,115 @@
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fs};
+
+use anyhow::{bail, Context, Result};
+use aoc::plugin::{Registry, PLUGIN_ABI_VERSION, PLUGIN_SYMBOL};
+use clap::{value_parser, Arg, ArgAction};
+use libloading::{Library, Symbol};
+
+static LOADED: AtomicUsize = AtomicUsize::new(0);
+
+pub fn plugin_arg() -> Arg {
+    Arg::new("plugin")
+        .long("plugin")
+        .global(true)
+        .action(ArgAction::Append)
+        .value_parser(value_parser!(PathBuf))
+        .help("Load solutions from this library instead of the built-in ones")
+}
+
+/// `target/debug/libyear2022.so` on Linux
+pub fn default_path(year: u16) -> PathBuf {
+    Path::new("target")
+        .join("debug")
+        .join(format!("{DLL_PREFIX}year{year}{DLL_SUFFIX}"))
+}
+
+/// Loads the registry of a plugin library. The library is loaded from a
+/// private copy, so it can be rebuilt and loaded again while the runner is
+/// running. Rust libraries can't be unloaded safely, so every loaded plugin
+/// stays in memory until the runner exits.
+pub fn load(path: &Path) -> Result<&'static Registry> {
+    let n = LOADED.fetch_add(1, Ordering::Relaxed);
+    let file_name = format!("aoc-plugin-{}-{n}{DLL_SUFFIX}", process::id());
+    let copy = env::temp_dir().join(file_name);
+    fs::copy(path, &copy).with_context(|| format!("copying {}", path.display()))?;
+
+    let library = unsafe { Library::new(&copy) };
+    // The mapping stays valid after the file is removed (not on Windows, where this fails)
+    let _ = fs::remove_file(&copy);
+    let library = library.with_context(|| format!("loading {}", path.display()))?;
+
+    let registry = unsafe {
+        let entry: Symbol<fn() -> &'static Registry> = library
+            .get(PLUGIN_SYMBOL)
+            .with_context(|| format!("{} is not a solution plugin", path.display()))?;
+        entry()
+    };
+    if registry.abi_version != PLUGIN_ABI_VERSION {
+        bail!(
+            "{} has plugin ABI version {}, expected {PLUGIN_ABI_VERSION}",
+            path.display(),
+            registry.abi_version
+        );
+    }
+    std::mem::forget(library);
+    Ok(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use aoc::solution::find_solution;
+    use serde_json::Value;
+
+    use super::*;
+
+    /// Builds the year2022 library like `cargo build` does for `aoc watch`,
+    /// which `cargo test` doesn't since the runner only links its rlib.
+    fn build_year2022() -> Result<PathBuf> {
+        let output = Command::new(env!("CARGO"))
+            .args(["build", "--package", "year2022", "--lib"])
+            .args(["--message-format", "json"])
+            .args((!cfg!(debug_assertions)).then_some("--release"))
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()?;
+        if !output.status.success() {
+            bail!("{}", String::from_utf8_lossy(&output.stderr));
+        }
+
+        for line in String::from_utf8(output.stdout)?.lines() {
+            let message: Value = serde_json::from_str(line)?;
+            if message["target"]["name"] != "year2022" {
+                continue;
+            }
+            let filenames = message["filenames"].as_array().into_iter().flatten();
+            if let Some(path) = filenames
+                .filter_map(Value::as_str)
+                .find(|f| f.ends_with(DLL_SUFFIX))
+            {
+                return Ok(PathBuf::from(path));
+            }
+        }
+        bail!("cargo built no year2022 library")
+    }
+
+    #[test]
+    fn test_load() -> Result<()> {
+        let registry = load(&build_year2022()?)?;
+        assert_eq!(registry.year, 2022);
+        let solution = find_solution(registry.solutions, 6).context("no day 6")?;
+        let solve = solution.part(1).context("no part 1")?;
+        assert_eq!(solve("mjqjpqmgbljsphdztnvjfqwrcgsmlb")?, "7");
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_invalid() {
+        assert!(load(Path::new("Cargo.toml")).is_err());
+        assert!(load(Path::new("missing.so")).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use std::io::{self, IsTerminal};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use aoc::alloc;
+use aoc::progress::{self, Snapshot};
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 30;
+
+/// Runs `f`, drawing the progress it reports as a bar on stderr if stderr is
+/// a terminal. The bar is cleared once `f` returns.
+pub fn show_while<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let progress = progress::current();
+    progress.reset();
+    if !io::stderr().is_terminal() {
+        return f();
+    }
+
+    let (sender, receiver) = mpsc::channel::<()>();
+    let (ready_sender, ready) = mpsc::channel();
+    let label = label.to_owned();
+    let drawer = thread::spawn(move || {
+        // Drawing allocates, which must not count towards what `f` allocates
+        alloc::ignore_current_thread();
+        let _ = ready_sender.send(());
+        let mut drawn = false;
+        while let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(REFRESH_INTERVAL) {
+            if let Some(snapshot) = progress.snapshot() {
+                eprint!("\r\x1b[2K{}", bar(&label, &snapshot));
+                drawn = true;
+            }
+        }
+        if drawn {
+            eprint!("\r\x1b[2K");
+        }
+    });
+    let _ = ready.recv();
+    let result = f();
+    drop(sender);
+    drawer.join().expect("drawing the bar doesn't panic");
+    result
+}
+
+fn bar(label: &str, snapshot: &Snapshot) -> String {
+    let ratio = match snapshot.total {
+        0 => 0.0,
+        total => (snapshot.done as f64 / total as f64).min(1.0),
+    };
+    let filled = (ratio * BAR_WIDTH as f64) as usize;
+    let eta = match snapshot.eta() {
+        Some(eta) => format_duration(eta),
+        None => "?".to_string(),
+    };
+    format!(
+        "  {label} [{}{}] {}/{} {:.0}% ETA {eta}",
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        snapshot.done,
+        snapshot.total,
+        ratio * 100.0
+    )
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        _ => format!("{}m{:02}s", secs / 60, secs % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc::progress::Snapshot;
+
+    use super::{bar, format_duration};
+
+    #[test]
+    fn test_bar() {
+        let snapshot = Snapshot {
+            done: 2500,
+            total: 10_000,
+            elapsed: Duration::from_secs(30),
+        };
+        assert_eq!(
+            bar("Part 2", &snapshot),
+            format!(
+                "  Part 2 [{}{}] 2500/10000 25% ETA 1m30s",
+                "#".repeat(7),
+                "-".repeat(23)
+            )
+        );
+
+        let snapshot = Snapshot {
+            done: 0,
+            ..snapshot
+        };
+        assert!(bar("Part 2", &snapshot).ends_with("0/10000 0% ETA ?"));
+        let snapshot = Snapshot {
+            total: 0,
+            ..snapshot
+        };
+        assert!(bar("Part 2", &snapshot).ends_with("0/0 0% ETA ?"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(5900)), "5s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m05s");
+    }
+}
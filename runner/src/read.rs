@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::{value_parser, Arg, ArgMatches, Command};
+
+use crate::cli;
+use crate::page::PuzzlePage;
+
+pub fn command() -> Command {
+    Command::new("read")
+        .about("Show a cached puzzle statement as Markdown")
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().required(true))
+        .arg(
+            Arg::new("import")
+                .long("import")
+                .value_parser(value_parser!(PathBuf))
+                .help("Cache this saved puzzle page first"),
+        )
+        .arg(
+            Arg::new("part")
+                .long("part")
+                .short('p')
+                .value_parser(value_parser!(u8).range(1..=2))
+                .help("Show only this part"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let year = *matches.get_one::<u16>("year").expect("required");
+    let day = *matches.get_one::<u8>("day").expect("required");
+
+    if let Some(path) = matches.get_one::<PathBuf>("import") {
+        let html =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let page = PuzzlePage::parse(&html);
+        if page.parts.is_empty() {
+            bail!("{} is not a puzzle page", path.display());
+        }
+        if !page.cache(year, day, &html)? {
+            eprintln!("The cached page already has more parts, keeping it");
+        }
+    }
+
+    let page = match PuzzlePage::load_cached(year, day)? {
+        Some(page) => page,
+        None => bail!("day {day} of {year} is not cached, save the puzzle page and use --import"),
+    };
+
+    let mut shown = vec![];
+    for (part, description) in (1..).zip(&page.parts) {
+        if matches.get_one::<u8>("part").is_none_or(|&p| p == part) {
+            shown.push(description.markdown.as_str());
+        }
+    }
+    if shown.is_empty() {
+        bail!("the cached page has no such part yet, import the page again after solving part 1");
+    }
+    print!("{}", shown.join("\n"));
+    Ok(())
+}
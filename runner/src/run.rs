@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use aoc::alloc;
+use aoc::inputs::{Answers, Input};
+use aoc::read_puzzle_data;
+use aoc::solution::{Solution, MAIN_VARIANT};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+
+use crate::attempts::{self, Attempt};
+use crate::cli;
+use crate::progress;
+
+pub fn command() -> Command {
+    Command::new("run")
+        .about("Run a solution on the inputs of the day")
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().required(true))
+        .arg(cli::label_arg().conflicts_with("input"))
+        .arg(
+            Arg::new("input")
+                .long("input")
+                .short('i')
+                .value_parser(value_parser!(PathBuf))
+                .help("Run on this file instead of the stored inputs"),
+        )
+        .arg(
+            Arg::new("save")
+                .long("save")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("input")
+                .help("Save the answers as the expected answers of the inputs"),
+        )
+        .arg(cli::all_variants_arg())
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let solution = cli::selected_solutions(matches)?[0];
+    let all_variants = matches.get_flag("all-variants");
+
+    if let Some(path) = matches.get_one::<PathBuf>("input") {
+        let data = read_puzzle_data(path).with_context(|| format!("reading {}", path.display()))?;
+        println!("Day {} [{}]", solution.day, path.display());
+        solve_all_parts(solution, &data, None, &Answers::new(), all_variants)?;
+        return Ok(());
+    }
+
+    let inputs = cli::selected_inputs(solution.day, matches)?;
+    if inputs.is_empty() {
+        println!("Day {} has no inputs", solution.day);
+    }
+    for input in inputs {
+        println!("Day {} [{}]", solution.day, input.label);
+        let expected = input.read_answers()?;
+        let data = input.read()?;
+        let answers = solve_all_parts(solution, &data, Some(&input), &expected, all_variants)?;
+        if matches.get_flag("save") {
+            input.write_answers(&answers)?;
+            println!("  Saved to {}", input.answers_path().display());
+        }
+    }
+    Ok(())
+}
+
+/// Solves every part and records the answers of the main implementations
+/// when `data` is the stored input `input`, other files don't count as
+/// attempts. With `all_variants`, the other implementations run too and must
+/// agree with the main one.
+pub fn solve_all_parts(
+    solution: &Solution,
+    data: &str,
+    input: Option<&Input>,
+    expected: &Answers,
+    all_variants: bool,
+) -> Result<Answers> {
+    let mut answers = Answers::new();
+    let mut disagreeing = Vec::new();
+    for part in 1..=solution.parts.len() as u8 {
+        let mut main_answer = None;
+        for (name, solve) in cli::part_variants(solution, part, all_variants) {
+            let prefix = match all_variants {
+                true => format!("Part {part} [{name}]"),
+                false => format!("Part {part}"),
+            };
+            let ((result, elapsed), alloc_stats) =
+                progress::show_while(&prefix, || alloc::measure(|| cli::timed(|| solve(data))));
+            let note = match alloc_stats {
+                Some(stats) => format!("({elapsed:?}, {stats})"),
+                None => format!("({elapsed:?})"),
+            };
+            let answer = match result {
+                Ok(answer) => {
+                    print_answer(&prefix, &answer, &note);
+                    Some(answer)
+                }
+                Err(e) => {
+                    println!("  {prefix}: error: {e:#}");
+                    None
+                }
+            };
+
+            if name != MAIN_VARIANT {
+                if answer != main_answer {
+                    disagreeing.push(format!("{part} [{name}]"));
+                }
+                continue;
+            }
+            if let Some(answer) = &answer {
+                if let Some(input) = input {
+                    let attempt = Attempt::new(
+                        solution.year,
+                        solution.day,
+                        &input.label,
+                        part,
+                        answer,
+                        expected.get(&part),
+                    );
+                    attempts::record(&attempt)?;
+                }
+                answers.insert(part, answer.clone());
+            }
+            main_answer = answer;
+        }
+    }
+    if !disagreeing.is_empty() {
+        bail!(
+            "variants disagree with the main part: {}",
+            disagreeing.join(", ")
+        );
+    }
+    Ok(answers)
+}
+
+pub fn print_answer(prefix: &str, answer: &str, note: &str) {
+    match answer.contains('\n') {
+        true => println!("  {prefix}: {note}\n{}", answer.trim_end()),
+        false => println!("  {prefix}: {answer} {note}"),
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use aoc::solution::{find_solution, Solution};
+use clap::{value_parser, Arg, ArgMatches, Command};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::cli;
+
+const MAX_INPUT_BYTES: u64 = 16 * 1024 * 1024;
+
+type Registry = BTreeMap<u16, &'static [Solution]>;
+
+pub fn command() -> Command {
+    Command::new("serve")
+        .about("Serve the solutions over HTTP")
+        .long_about(
+            "Serve the solutions over HTTP.\n\n\
+             POST /<year>/<day>/<part> with the input as the body returns the answer and \
+             the time it took as JSON. GET /days lists the implemented days.",
+        )
+        .arg(
+            Arg::new("port")
+                .long("port")
+                .short('p')
+                .value_parser(value_parser!(u16))
+                .default_value("8080"),
+        )
+        .arg(
+            Arg::new("bind")
+                .long("bind")
+                .value_parser(value_parser!(IpAddr))
+                .default_value("127.0.0.1")
+                .help("Address to listen on, use 0.0.0.0 to accept remote connections"),
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .value_parser(value_parser!(u64).range(1..))
+                .default_value("30")
+                .help("Seconds a solution may run before the request fails"),
+        )
+        .arg(
+            Arg::new("max-solves")
+                .long("max-solves")
+                .value_parser(value_parser!(u64).range(1..))
+                .help(
+                    "Solutions that may run at once, counting those that timed out, \
+                     the number of CPUs by default",
+                ),
+        )
+}
+
+/// Limits the solutions running at once. Those whose request timed out keep
+/// their slot until they finish, since they can't be interrupted.
+#[derive(Clone)]
+struct Solver {
+    timeout: Duration,
+    running: Arc<AtomicUsize>,
+    max_running: usize,
+}
+
+/// Frees its slot when dropped, including when the solution panics.
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Solver {
+    fn new(timeout: Duration, max_running: usize) -> Self {
+        Self {
+            timeout,
+            running: Arc::new(AtomicUsize::new(0)),
+            max_running,
+        }
+    }
+
+    fn acquire(&self) -> Option<Slot> {
+        self.running
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                (running < self.max_running).then_some(running + 1)
+            })
+            .ok()
+            .map(|_| Slot(self.running.clone()))
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let ip = *matches.get_one::<IpAddr>("bind").expect("has default");
+    let port = *matches.get_one::<u16>("port").expect("has default");
+    let timeout = Duration::from_secs(*matches.get_one::<u64>("timeout").expect("has default"));
+    let max_solves = match matches.get_one::<u64>("max-solves") {
+        Some(&max_solves) => max_solves as usize,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let solver = Solver::new(timeout, max_solves);
+    let registry: Registry = cli::all_solutions(matches)?.into_iter().collect();
+
+    let server = Server::http(SocketAddr::new(ip, port)).map_err(|e| anyhow!(e))?;
+    if ip != IpAddr::V4(Ipv4Addr::LOCALHOST) {
+        println!("warning: accepting connections from other hosts");
+    }
+    println!("Listening on http://{}", server.server_addr());
+
+    for request in server.incoming_requests() {
+        let registry = registry.clone();
+        let solver = solver.clone();
+        thread::spawn(move || handle(request, &registry, &solver));
+    }
+    Ok(())
+}
+
+fn handle(mut request: Request, registry: &Registry, solver: &Solver) {
+    let (status, body) = route(&mut request, registry, solver);
+    println!("{} {} {status}", request.method(), request.url());
+    let header = Header::from_bytes("Content-Type", "application/json").expect("valid header");
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        println!("error: {e}");
+    }
+}
+
+fn route(request: &mut Request, registry: &Registry, solver: &Solver) -> (u16, Value) {
+    let segments: Vec<&str> = request.url().trim_matches('/').split('/').collect();
+    match (request.method(), segments.as_slice()) {
+        (Method::Get, ["days"]) => (200, days(registry)),
+        (Method::Post, [year, day, part]) => {
+            let puzzle = (year.parse(), day.parse(), part.parse());
+            let (Ok(year), Ok(day), Ok(part)) = puzzle else {
+                return error(404, "not found");
+            };
+            match read_input(request) {
+                Ok(input) => solve(registry, year, day, part, input, solver),
+                Err(response) => response,
+            }
+        }
+        (_, ["days"] | [_, _, _]) => error(405, "method not allowed"),
+        _ => error(404, "not found"),
+    }
+}
+
+fn read_input(request: &mut Request) -> Result<String, (u16, Value)> {
+    let mut input = Vec::new();
+    let read = request
+        .as_reader()
+        .take(MAX_INPUT_BYTES + 1)
+        .read_to_end(&mut input);
+    match read {
+        Err(e) => Err(error(400, &format!("reading the input: {e}"))),
+        Ok(n) if n as u64 > MAX_INPUT_BYTES => Err(error(413, "input is too large")),
+        Ok(_) => String::from_utf8(input).map_err(|_| error(400, "input is not valid UTF-8")),
+    }
+}
+
+fn days(registry: &Registry) -> Value {
+    let days: Vec<Value> = registry
+        .values()
+        .flat_map(|solutions| solutions.iter())
+        .map(|s| json!({"year": s.year, "day": s.day, "parts": s.parts.len()}))
+        .collect();
+    Value::Array(days)
+}
+
+fn solve(
+    registry: &Registry,
+    year: u16,
+    day: u8,
+    part: u8,
+    input: String,
+    solver: &Solver,
+) -> (u16, Value) {
+    let solution = registry.get(&year).and_then(|s| find_solution(s, day));
+    let Some(solve) = solution.and_then(|s| s.part(part)) else {
+        return error(
+            404,
+            &format!("{year} day {day} part {part} is not implemented"),
+        );
+    };
+
+    let Some(slot) = solver.acquire() else {
+        return error(503, "too many solutions running, try again later");
+    };
+    // The solution can't be interrupted, so on timeout its thread keeps running in the background
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _slot = slot;
+        let _ = sender.send(cli::timed(|| solve(&input)));
+    });
+    let timeout = solver.timeout;
+    match receiver.recv_timeout(timeout) {
+        Ok((Ok(answer), elapsed)) => {
+            let body = json!({
+                "year": year,
+                "day": day,
+                "part": part,
+                "answer": answer,
+                "elapsed_ms": elapsed.as_secs_f64() * 1000.0,
+            });
+            (200, body)
+        }
+        Ok((Err(e), _)) => error(422, &format!("{e:#}")),
+        Err(RecvTimeoutError::Timeout) => error(504, &format!("timed out after {timeout:?}")),
+        Err(RecvTimeoutError::Disconnected) => error(500, "the solution panicked"),
+    }
+}
+
+fn error(status: u16, message: &str) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let registry = Registry::from([(year2022::YEAR, year2022::SOLUTIONS)]);
+        let solver = Solver::new(Duration::from_secs(10), 4);
+
+        let (status, body) = solve(&registry, 2022, 2, 1, "A Y\nB X\nC Z\n".into(), &solver);
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], "15");
+
+        let (status, body) = solve(&registry, 2022, 10, 1, "jump 3\n".into(), &solver);
+        assert_eq!(status, 422);
+        assert_eq!(body["error"], "undefined instruction");
+
+        assert_eq!(solve(&registry, 2022, 2, 3, String::new(), &solver).0, 404);
+        assert_eq!(solve(&registry, 2015, 1, 1, String::new(), &solver).0, 404);
+        assert_eq!(
+            days(&registry)[0],
+            json!({"year": 2022, "day": 1, "parts": 2})
+        );
+    }
+
+    #[test]
+    fn test_max_solves() {
+        let registry = Registry::from([(year2022::YEAR, year2022::SOLUTIONS)]);
+        let solver = Solver::new(Duration::from_secs(10), 2);
+        let first = solver.acquire().unwrap();
+        let _second = solver.acquire().unwrap();
+        assert!(solver.acquire().is_none());
+
+        let (status, body) = solve(&registry, 2022, 2, 1, "A Y\n".into(), &solver);
+        assert_eq!(status, 503);
+        assert_eq!(body["error"], "too many solutions running, try again later");
+
+        drop(first);
+        assert_eq!(solve(&registry, 2022, 2, 1, "A Y\n".into(), &solver).0, 200);
+    }
+}
@@ -0,0 +1,308 @@
+use std::cell::RefCell;
+use std::io::{self, Read, Write};
+use std::mem::{self, Discriminant};
+use std::panic;
+use std::path::PathBuf;
+use std::process::{self, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+use anyhow::{bail, Context, Result};
+use aoc::difftest::{self, Outcome};
+use aoc::inputs::{Answers, Input};
+use aoc::read_puzzle_data;
+use aoc::shrink::shrink;
+use aoc::solution::Part;
+use clap::{value_parser, Arg, ArgMatches, Command, ValueHint};
+
+use crate::cli;
+
+pub fn command() -> Command {
+    Command::new("shrink")
+        .about("Shrink an input a part fails on and save it as a regression example")
+        .long_about(
+            "Shrink an input a part fails on and save it as a regression example. A part \
+             fails when it panics, hangs or disagrees with its reference implementation. \
+             Lines, blocks and numbers of the input are removed or made smaller as long as \
+             the part keeps failing the same way, and the result is saved to \
+             year<YEAR>/test_data/regressions. Every shrunk input is run in a new runner \
+             process, which is killed once it runs for longer than the timeout.",
+        )
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().required(true))
+        .arg(
+            Arg::new("part")
+                .required(true)
+                .value_parser(value_parser!(u8).range(1..)),
+        )
+        .arg(
+            Arg::new("input")
+                .long("input")
+                .short('i')
+                .required_unless_present("candidate")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath)
+                .help("The input the part fails on"),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .help("Name of the regression example, the name of the input file by default"),
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .value_parser(value_parser!(u64))
+                .default_value("10")
+                .help("Seconds the part can run on an input before it counts as hanging"),
+        )
+        .arg(
+            // Runs the part or its reference on the input from stdin and prints
+            // the outcome, for the parent runner
+            Arg::new("candidate")
+                .long("candidate")
+                .hide(true)
+                .value_parser(["part", "reference"]),
+        )
+}
+
+thread_local! {
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// How the part fails on the original input, shrunk inputs must fail the same way.
+enum Failure {
+    /// Panics at this location with this message, numbers aside.
+    Panic(String),
+    /// Disagrees with the reference, with outcomes of these kinds.
+    Mismatch(Discriminant<Outcome>, Discriminant<Outcome>),
+    /// Runs for longer than the timeout.
+    Timeout,
+}
+
+/// What running the part or its reference in a runner process gave.
+enum Run {
+    /// The outcome and where the part panicked.
+    Finished(Outcome, Option<String>),
+    TimedOut,
+}
+
+struct Runner {
+    exe: PathBuf,
+    /// Arguments selecting the part in a runner process.
+    args: Vec<String>,
+    has_reference: bool,
+    timeout: Duration,
+}
+
+impl Runner {
+    /// Runs the part, or its reference, on the input in a new runner process
+    /// and kills it if it runs for longer than the timeout. Hanging parts can't
+    /// be stopped on a thread, and every one left behind would keep running.
+    fn outcome(&self, candidate: &str, input: &str) -> Result<Run> {
+        let mut child = process::Command::new(&self.exe)
+            .args(&self.args)
+            .args(["--candidate", candidate])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("starting the runner")?;
+        let mut stdin = child.stdin.take().expect("piped");
+        // The part may fail before it reads all of the input
+        let _ = stdin.write_all(input.as_bytes());
+        drop(stdin);
+        let mut stdout = child.stdout.take().expect("piped");
+        let reader = thread::spawn(move || {
+            let mut output = String::new();
+            stdout.read_to_string(&mut output).map(|_| output)
+        });
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+                let _ = reader.join();
+                return Ok(Run::TimedOut);
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        let output = reader.join().expect("reader doesn't panic")?;
+        // A part that aborts, like on a stack overflow, prints nothing
+        Ok(parse_outcome(&output).unwrap_or_else(|| {
+            Run::Finished(Outcome::Panic(format!("runner exited with {status}")), None)
+        }))
+    }
+
+    fn failure(&self, input: &str) -> Result<Option<Failure>> {
+        let Run::Finished(answer, location) = self.outcome("part", input)? else {
+            return Ok(Some(Failure::Timeout));
+        };
+        if let Outcome::Panic(message) = &answer {
+            let location = location.unwrap_or_default();
+            return Ok(Some(Failure::Panic(format!(
+                "{} at {location}",
+                without_numbers(message)
+            ))));
+        }
+        if !self.has_reference {
+            return Ok(None);
+        }
+        // A reference that hangs tells nothing about the part
+        let Run::Finished(expected, _) = self.outcome("reference", input)? else {
+            return Ok(None);
+        };
+        Ok((!answer.agrees_with(&expected))
+            .then(|| Failure::Mismatch(mem::discriminant(&answer), mem::discriminant(&expected))))
+    }
+
+    fn fails(&self, input: &str, failure: &Failure) -> bool {
+        match (self.failure(input), failure) {
+            (Ok(Some(Failure::Panic(m1))), Failure::Panic(m2)) => m1 == *m2,
+            (Ok(Some(Failure::Mismatch(a1, r1))), Failure::Mismatch(a2, r2)) => {
+                a1 == *a2 && r1 == *r2
+            }
+            (Ok(Some(Failure::Timeout)), Failure::Timeout) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Runs the part on the input from stdin and prints where it panicked, the
+/// kind of outcome and the answer or message, one after the other.
+fn run_candidate(part: Part) -> Result<()> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    // Records where the part panics instead of printing it
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().map(|l| l.to_string());
+        PANIC_LOCATION.set(location);
+    }));
+    let outcome = difftest::outcome(part, &input);
+    let (kind, text) = match &outcome {
+        Outcome::Answer(answer) => ("answer", answer),
+        Outcome::Error(e) => ("error", e),
+        Outcome::Panic(message) => ("panic", message),
+    };
+    let location = PANIC_LOCATION.take().unwrap_or_default();
+    print!("{location}\n{kind}\n{text}");
+    Ok(())
+}
+
+fn parse_outcome(output: &str) -> Option<Run> {
+    let mut fields = output.splitn(3, '\n');
+    let location = fields.next()?;
+    let (kind, text) = (fields.next()?, fields.next()?.to_string());
+    let outcome = match kind {
+        "answer" => Outcome::Answer(text),
+        "error" => Outcome::Error(text),
+        "panic" => Outcome::Panic(text),
+        _ => return None,
+    };
+    let location = (!location.is_empty()).then(|| location.to_string());
+    Some(Run::Finished(outcome, location))
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let year = *matches.get_one::<u16>("year").expect("required");
+    let solution = cli::selected_solutions(matches)?[0];
+    let part_number = *matches.get_one::<u8>("part").expect("required");
+    let Some(part) = solution.part(part_number) else {
+        bail!("day {} has no part {part_number}", solution.day);
+    };
+    let reference = solution.reference(part_number);
+    match matches.get_one::<String>("candidate").map(String::as_str) {
+        Some("part") => return run_candidate(part),
+        Some(_) => return run_candidate(reference.context("the part has no reference")?),
+        None => {}
+    }
+
+    let path = matches.get_one::<PathBuf>("input").expect("required");
+    let input = read_puzzle_data(path).with_context(|| format!("reading {}", path.display()))?;
+    let mut args = Vec::new();
+    for plugin in matches.get_many::<PathBuf>("plugin").into_iter().flatten() {
+        args.extend(["--plugin".to_string(), plugin.display().to_string()]);
+    }
+    args.extend([
+        "shrink".to_string(),
+        year.to_string(),
+        solution.day.to_string(),
+        part_number.to_string(),
+    ]);
+    let runner = Runner {
+        exe: env::current_exe().context("locating the runner")?,
+        args,
+        has_reference: reference.is_some(),
+        timeout: Duration::from_secs(*matches.get_one::<u64>("timeout").expect("has default")),
+    };
+
+    let Some(failure) = runner.failure(&input)? else {
+        bail!(
+            "day {} part {part_number} doesn't fail on {}",
+            solution.day,
+            path.display()
+        );
+    };
+    let shrunk = shrink(&input, |candidate| runner.fails(candidate, &failure));
+
+    println!(
+        "Shrunk from {} to {} lines ({} to {} bytes)",
+        input.lines().count(),
+        shrunk.lines().count(),
+        input.len(),
+        shrunk.len()
+    );
+    for line in shrunk.lines() {
+        println!("  {line}");
+    }
+
+    let name = match matches.get_one::<String>("name") {
+        Some(name) => name.clone(),
+        None => path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+    let example = Input::regression(year, solution.day, &name);
+    if example.path.exists() {
+        bail!(
+            "{} already exists, pick another --name",
+            example.path.display()
+        );
+    }
+    if let Some(dir) = example.path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&example.path, &shrunk)?;
+    println!("Saved to {}", example.path.display());
+
+    let expected = match runner.has_reference {
+        true => Some(runner.outcome("reference", &shrunk)?),
+        false => None,
+    };
+    if let Some(Run::Finished(Outcome::Answer(answer), _)) = expected {
+        example.write_answers(&Answers::from([(part_number, answer)]))?;
+        println!(
+            "Reference answer saved to {}",
+            example.answers_path().display()
+        );
+    }
+    Ok(())
+}
+
+fn without_numbers(message: &str) -> String {
+    let mut result = String::new();
+    for c in message.chars() {
+        match c.is_ascii_digit() {
+            true if result.ends_with('#') => {}
+            true => result.push('#'),
+            false => result.push(c),
+        }
+    }
+    result
+}
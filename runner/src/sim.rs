@@ -0,0 +1,129 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use aoc::inputs::Input;
+use aoc::read_puzzle_data;
+use aoc::sim::{read_trace, replay, Driver, Simulation};
+use clap::{value_parser, Arg, ArgMatches, Command, ValueHint};
+
+use crate::cli;
+
+pub fn command() -> Command {
+    Command::new("sim")
+        .about("Run the simulation of a part step by step")
+        .long_about(
+            "Run the simulation of a part step by step and draw where it ends. Runs on the \
+             example of the day unless an input is given. States can be recorded to a trace \
+             file, one per line, and a trace drawn again step by step with --replay.",
+        )
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().required(true))
+        .arg(
+            Arg::new("part")
+                .required(true)
+                .value_parser(value_parser!(u8).range(1..)),
+        )
+        .args(input_args())
+        .arg(
+            Arg::new("steps")
+                .long("steps")
+                .short('n')
+                .value_parser(value_parser!(usize))
+                .help("Stop after this many steps, run until the simulation is done by default"),
+        )
+        .arg(
+            Arg::new("record")
+                .long("record")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath)
+                .help("Write the state after every step to this trace file"),
+        )
+        .arg(
+            Arg::new("replay")
+                .long("replay")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath)
+                .conflicts_with_all(["steps", "record"])
+                .help("Draw every step of this trace file instead of running"),
+        )
+}
+
+/// Arguments picking the input of a simulation.
+pub fn input_args() -> [Arg; 2] {
+    [
+        cli::label_arg()
+            .conflicts_with("input")
+            .help("Use the input with this label, the example of the day by default"),
+        Arg::new("input")
+            .long("input")
+            .short('i')
+            .value_parser(value_parser!(PathBuf))
+            .value_hint(ValueHint::FilePath)
+            .help("Use this file instead of the example of the day"),
+    ]
+}
+
+/// Creates the simulation of the selected part on the selected input.
+pub fn load_simulation(matches: &ArgMatches) -> Result<Box<dyn Simulation>> {
+    let solution = cli::selected_solutions(matches)?[0];
+    let part = *matches.get_one::<u8>("part").expect("required");
+    let Some(load) = solution.simulation else {
+        bail!("day {} has no simulation", solution.day);
+    };
+    if solution.part(part).is_none() {
+        bail!("day {} has no part {part}", solution.day);
+    }
+
+    let data = match matches.get_one::<PathBuf>("input") {
+        Some(path) => {
+            read_puzzle_data(path).with_context(|| format!("reading {}", path.display()))?
+        }
+        None if matches.contains_id("label") => {
+            cli::selected_inputs(solution.day, matches)?[0].read()?
+        }
+        None => {
+            let input = Input::example(solution.year, solution.day, 1);
+            input
+                .read()
+                .with_context(|| format!("reading {}", input.path.display()))?
+        }
+    };
+    load(&data, part)
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let mut simulation = load_simulation(matches)?;
+
+    if let Some(path) = matches.get_one::<PathBuf>("replay") {
+        let file = File::open(path).with_context(|| format!("reading {}", path.display()))?;
+        let states = read_trace(BufReader::new(file))?;
+        for (step, frame) in replay(simulation.as_mut(), &states)?.iter().enumerate() {
+            println!("Step {step}");
+            print!("{frame}");
+        }
+        return Ok(());
+    }
+
+    let mut driver = Driver::new(simulation);
+    let record = matches.get_one::<PathBuf>("record");
+    if let Some(path) = record {
+        let file = File::create(path).with_context(|| format!("writing {}", path.display()))?;
+        driver.record(BufWriter::new(file))?;
+    }
+    match matches.get_one::<usize>("steps") {
+        Some(&steps) => driver.run_steps(steps)?,
+        None => driver.run()?,
+    };
+
+    print!("{}", driver.simulation().render());
+    match driver.simulation().done() {
+        true => println!("Done after {} steps", driver.steps()),
+        false => println!("Stopped after {} steps", driver.steps()),
+    }
+    if let Some(path) = record {
+        println!("Trace written to {}", path.display());
+    }
+    Ok(())
+}
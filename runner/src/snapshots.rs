@@ -0,0 +1,91 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use aoc::snapshot::{self, pending_path, snapshot_path, snapshots_dir};
+use clap::{Arg, ArgMatches, Command};
+
+use crate::cli;
+
+pub fn command() -> Command {
+    let names = Arg::new("names")
+        .num_args(0..)
+        .help("Snapshots to process, all pending ones by default");
+
+    Command::new("snapshots")
+        .about("Review the rendered outputs changed by snapshot tests")
+        .long_about(
+            "Review the rendered outputs changed by snapshot tests. Tests that render \
+             something different from their snapshot in test_data/snapshots save the new \
+             output next to it and fail until it is accepted or rejected here.",
+        )
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("review")
+                .about("Show the difference between pending outputs and their snapshots")
+                .arg(cli::year_arg())
+                .arg(names.clone()),
+        )
+        .subcommand(
+            Command::new("accept")
+                .about("Replace snapshots with their pending outputs")
+                .arg(cli::year_arg())
+                .arg(names.clone()),
+        )
+        .subcommand(
+            Command::new("reject")
+                .about("Discard pending outputs")
+                .arg(cli::year_arg())
+                .arg(names),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let (action, m) = matches.subcommand().expect("subcommand is required");
+    let dir = snapshots_dir(*m.get_one::<u16>("year").expect("required"));
+    let names = selected_names(&dir, m)?;
+    if names.is_empty() {
+        println!("No pending snapshots");
+        return Ok(());
+    }
+
+    for name in &names {
+        match action {
+            "review" => review(&dir, name)?,
+            "accept" => {
+                snapshot::accept(&dir, name)?;
+                println!("Accepted {}", snapshot_path(&dir, name).display());
+            }
+            "reject" => {
+                snapshot::reject(&dir, name)?;
+                println!("Rejected {}", pending_path(&dir, name).display());
+            }
+            _ => unreachable!("no other subcommands"),
+        }
+    }
+    Ok(())
+}
+
+fn selected_names(dir: &Path, matches: &ArgMatches) -> Result<Vec<String>> {
+    let pending = snapshot::pending(dir)?;
+    let Some(names) = matches.get_many::<String>("names") else {
+        return Ok(pending);
+    };
+    let names: Vec<String> = names.cloned().collect();
+    for name in &names {
+        if !pending.contains(name) {
+            bail!("snapshot {name} has no pending output in {}", dir.display());
+        }
+    }
+    Ok(names)
+}
+
+fn review(dir: &Path, name: &str) -> Result<()> {
+    let new = fs::read_to_string(pending_path(dir, name))
+        .with_context(|| format!("reading {}", pending_path(dir, name).display()))?;
+    match fs::read_to_string(snapshot_path(dir, name)) {
+        Ok(old) => print!("--- {name}\n{}", snapshot::diff(&old, &new)),
+        Err(_) => print!("--- {name} (new)\n{}", snapshot::diff("", &new)),
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use aoc::inputs;
+use chrono::{DateTime, Utc};
+use clap::{value_parser, Arg, ArgMatches, Command};
+
+use crate::attempts;
+use crate::cli;
+use crate::event::{self, format_countdown, unlock_time, DAYS};
+
+const DAYS_PER_ROW: u8 = 5;
+
+pub fn command() -> Command {
+    Command::new("today")
+        .about("Show the current puzzle, the next unlock and a star calendar")
+        .arg(
+            Arg::new("year")
+                .long("year")
+                .value_parser(value_parser!(u16))
+                .help("Calendar year, the latest event by default"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let now = Utc::now();
+    match event::event_day(now) {
+        Some((year, day)) => println!("Today is day {day} of Advent of Code {year}"),
+        None => println!("No Advent of Code event is running"),
+    }
+    let (year, day, unlock) = event::next_unlock(now);
+    let countdown = format_countdown((unlock - now).num_seconds());
+    println!("Day {day} of {year} unlocks in {countdown}\n");
+
+    let year = match matches.get_one::<u16>("year") {
+        Some(&year) => year,
+        None => event::latest_event_year(now),
+    };
+    print_calendar(year, now)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Star {
+    Locked,
+    Open,
+    Attempted,
+    Solved,
+}
+
+impl Star {
+    fn symbol(self) -> char {
+        match self {
+            Star::Locked => ' ',
+            Star::Open => '.',
+            Star::Attempted => '?',
+            Star::Solved => '*',
+        }
+    }
+}
+
+fn print_calendar(year: u16, now: DateTime<Utc>) -> Result<()> {
+    let solved = solved_parts(year)?;
+    let attempted: HashSet<(u8, u8)> = attempts::load()?
+        .into_iter()
+        .filter(|a| a.year == year)
+        .map(|a| (a.day, a.part))
+        .collect();
+
+    println!("Advent of Code {year}");
+    let mut stars = 0;
+    for first in (1..=DAYS).step_by(DAYS_PER_ROW.into()) {
+        let mut row = String::new();
+        for day in first..(first + DAYS_PER_ROW).min(DAYS + 1) {
+            let symbols: String = [1, 2]
+                .into_iter()
+                .map(|part| {
+                    let star = match (day, part) {
+                        _ if now < unlock_time(year, day) => Star::Locked,
+                        key if solved.contains(&key) => Star::Solved,
+                        key if attempted.contains(&key) => Star::Attempted,
+                        _ => Star::Open,
+                    };
+                    stars += usize::from(star == Star::Solved);
+                    star.symbol()
+                })
+                .collect();
+            row.push_str(&format!("{day:>4} {symbols}"));
+        }
+        println!("{}", row.trim_end());
+    }
+    println!("\n{stars} stars  (* solved, ? attempted, . open)");
+    Ok(())
+}
+
+/// Parts with an expected answer for any input, or a correct attempt.
+fn solved_parts(year: u16) -> Result<HashSet<(u8, u8)>> {
+    let mut solved: HashSet<(u8, u8)> = attempts::load()?
+        .into_iter()
+        .filter(|a| a.year == year && a.correct == Some(true))
+        .map(|a| (a.day, a.part))
+        .collect();
+
+    // The inputs in data/ belong to the years we have solutions for
+    if cli::solutions_of(year).is_some() {
+        for day in 1..=DAYS {
+            for input in inputs::day_inputs(day)? {
+                solved.extend(input.read_answers()?.into_keys().map(|part| (day, part)));
+            }
+        }
+    }
+    Ok(solved)
+}
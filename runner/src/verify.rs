@@ -0,0 +1,84 @@
+use anyhow::{bail, Result};
+use aoc::solution::MAIN_VARIANT;
+use clap::{ArgMatches, Command};
+
+use crate::attempts::{self, Attempt};
+use crate::cli;
+use crate::progress;
+
+pub fn command() -> Command {
+    Command::new("verify")
+        .about("Check solutions against the expected answers of their inputs")
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().help("Verify only this day, all implemented days by default"))
+        .arg(cli::label_arg())
+        .arg(cli::all_variants_arg())
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let all_variants = matches.get_flag("all-variants");
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+
+    for solution in cli::selected_solutions(matches)? {
+        for input in cli::selected_inputs(solution.day, matches)? {
+            let data = input.read()?;
+            let expected = input.read_answers()?;
+
+            for part in 1..=solution.parts.len() as u8 {
+                let mut main_answer = None;
+                for (name, solve) in cli::part_variants(solution, part, all_variants) {
+                    let mut prefix = format!("Day {} [{}] part {part}", solution.day, input.label);
+                    if all_variants {
+                        prefix.push_str(&format!(" [{name}]"));
+                    }
+                    let (result, elapsed) =
+                        progress::show_while(&prefix, || cli::timed(|| solve(&data)));
+                    if name == MAIN_VARIANT {
+                        if let Ok(answer) = &result {
+                            let attempt = Attempt::new(
+                                solution.year,
+                                solution.day,
+                                &input.label,
+                                part,
+                                answer,
+                                expected.get(&part),
+                            );
+                            attempts::record(&attempt)?;
+                        }
+                        main_answer = result.as_ref().ok().cloned();
+                    }
+                    // Without an expected answer, the other variants are checked against the main one
+                    let expected = match (expected.get(&part), name) {
+                        (None, MAIN_VARIANT) => None,
+                        (None, _) => main_answer.as_ref(),
+                        (expected, _) => expected,
+                    };
+                    match (result, expected) {
+                        (Err(e), _) => {
+                            println!("{prefix}: FAILED with error: {e:#}");
+                            failed += 1;
+                        }
+                        (Ok(_), None) => {
+                            println!("{prefix}: no expected answer");
+                            unknown += 1;
+                        }
+                        (Ok(answer), Some(expected)) if answer == *expected => {
+                            println!("{prefix}: ok ({elapsed:?})");
+                            passed += 1;
+                        }
+                        (Ok(answer), Some(expected)) => {
+                            println!("{prefix}: FAILED, expected {expected:?}, got {answer:?}");
+                            failed += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    println!("\n{passed} passed, {failed} failed, {unknown} without expected answers");
+    if failed > 0 {
+        bail!("{failed} answers are wrong");
+    }
+    Ok(())
+}
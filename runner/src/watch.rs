@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{bail, Result};
+use aoc::inputs::Answers;
+use aoc::solution::find_solution;
+use clap::{value_parser, Arg, ArgMatches, Command};
+
+use crate::{cli, plugin, run};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn command() -> Command {
+    Command::new("watch")
+        .about("Run a solution from a plugin again every time the plugin is rebuilt")
+        .long_about(
+            "Run a solution from a plugin again every time the plugin is rebuilt. \
+             The plugin is target/debug/libyear<YEAR>.so by default, \
+             build it with `cargo build -p year<YEAR> --lib`.",
+        )
+        .arg(cli::year_arg())
+        .arg(cli::day_arg().required(true))
+        .arg(cli::label_arg())
+        .arg(
+            Arg::new("poll")
+                .long("poll")
+                .value_parser(value_parser!(u64))
+                .help("Check the plugin for changes every this many milliseconds"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<()> {
+    let year = *matches.get_one::<u16>("year").expect("required");
+    let day = *matches.get_one::<u8>("day").expect("required");
+    let path = match matches.get_one::<PathBuf>("plugin") {
+        Some(path) => path.clone(),
+        None => plugin::default_path(year),
+    };
+    let interval = match matches.get_one::<u64>("poll") {
+        Some(&ms) => Duration::from_millis(ms),
+        None => POLL_INTERVAL,
+    };
+
+    println!("Watching {}", path.display());
+    let mut loaded = None;
+    loop {
+        let modified = wait_for_change(&path, loaded, interval);
+        loaded = Some(modified);
+        if let Err(e) = run_plugin(&path, year, day, matches) {
+            println!("error: {e:#}");
+        }
+        println!("Waiting for {} to change", path.display());
+    }
+}
+
+fn run_plugin(path: &Path, year: u16, day: u8, matches: &ArgMatches) -> Result<()> {
+    let registry = plugin::load(path)?;
+    if registry.year != year {
+        bail!(
+            "{} has solutions for {}, not {year}",
+            path.display(),
+            registry.year
+        );
+    }
+    let Some(solution) = find_solution(registry.solutions, day) else {
+        bail!("day {day} is not implemented");
+    };
+
+    let inputs = cli::selected_inputs(day, matches)?;
+    if inputs.is_empty() {
+        println!("Day {day} has no inputs");
+    }
+    for input in inputs {
+        println!("Day {day} [{}]", input.label);
+        let expected = input.read_answers()?;
+        let data = input.read()?;
+        let answers = run::solve_all_parts(solution, &data, Some(&input), &expected, false)?;
+        report_mismatches(&answers, &expected);
+    }
+    Ok(())
+}
+
+fn report_mismatches(answers: &Answers, expected: &Answers) {
+    for (part, answer) in answers {
+        match expected.get(part) {
+            Some(e) if e != answer => println!("  Part {part}: expected {e}"),
+            _ => {}
+        }
+    }
+}
+
+/// Waits until the file has a modification time other than `last`, and has
+/// not been modified for one poll interval, so a library that is still being
+/// written is not loaded.
+fn wait_for_change(path: &Path, last: Option<SystemTime>, interval: Duration) -> SystemTime {
+    let mut seen = None;
+    loop {
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        match modified {
+            Some(m) if Some(m) != last && seen == Some(m) => return m,
+            _ => seen = modified,
+        }
+        thread::sleep(interval);
+    }
+}
@@ -0,0 +1,636 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+
+use tempfile::TempDir;
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head><title>Day 2 - Advent of Code 2022</title></head>
+<body><main>
+<article class="day-desc"><h2>--- Day 2: Rock Paper Scissors ---</h2>
+<p>For example, suppose you were given the following strategy guide:</p>
+<pre><code>A Y
+B X
+C Z
+</code></pre>
+<p>In this example, you would get a total score of <code><em>15</em></code>.</p>
+</article>
+</main></body>
+</html>"#;
+
+const LEADERBOARD: &str = r#"{
+    "event": "2022",
+    "owner_id": 1,
+    "members": {
+        "1": {
+            "id": 1, "name": "Alice", "stars": 2, "local_score": 2, "global_score": 0,
+            "last_star_ts": 1669871700,
+            "completion_day_level": {
+                "1": {
+                    "1": {"get_star_ts": 1669871400, "star_index": 1},
+                    "2": {"get_star_ts": 1669871700, "star_index": 2}
+                }
+            }
+        }
+    }
+}"#;
+
+/// A temporary working directory, so the runner's data and cache stay out of the repo.
+struct Workspace {
+    dir: TempDir,
+}
+
+impl Workspace {
+    fn new() -> Self {
+        Self {
+            dir: tempfile::tempdir().unwrap(),
+        }
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        self.dir.path().join(path)
+    }
+
+    fn write(&self, path: &str, contents: &str) {
+        let path = self.path(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_aoc"));
+        command
+            .args(args)
+            .current_dir(self.dir.path())
+            .env_remove("AOC_KEY")
+            .env("AOC_KEY_FILE", self.path(".aoc-key"));
+        command
+    }
+
+    fn aoc(&self, args: &[&str]) -> Output {
+        self.command(args).output().unwrap()
+    }
+
+    fn spawn(&self, args: &[&str]) -> Child {
+        self.command(args).stdout(Stdio::piped()).spawn().unwrap()
+    }
+}
+
+fn example(day: u8) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../year2022/test_data")
+        .join(format!("day{day}.txt"));
+    fs::read_to_string(path).unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn assert_success(output: &Output) -> String {
+    assert!(output.status.success(), "{}", stderr(output));
+    stdout(output)
+}
+
+fn assert_error(output: &Output, message: &str) {
+    assert_eq!(output.status.code(), Some(1), "{}", stdout(output));
+    assert!(
+        stderr(output).contains(message),
+        "expected {message:?} in {}",
+        stderr(output)
+    );
+}
+
+#[test]
+fn test_run() {
+    let ws = Workspace::new();
+    ws.write("example.txt", &example(1));
+
+    let stdout = assert_success(&ws.aoc(&["run", "2022", "1", "-i", "example.txt"]));
+    assert!(stdout.starts_with("Day 1 [example.txt]\n"));
+    assert!(stdout.contains("  Part 1: 24000 ("));
+    assert!(stdout.contains("  Part 2: 45000 ("));
+    // Only runs on the stored inputs count as attempts
+    assert!(!ws.path(".aoc-cache/attempts.jsonl").exists());
+
+    // Day 11 reports its progress, but stderr is not a terminal here
+    ws.write("example.txt", &example(11));
+    let output = ws.aoc(&["run", "2022", "11", "-i", "example.txt"]);
+    assert!(assert_success(&output).contains("  Part 2: 2713310158 ("));
+    assert_eq!(stderr(&output), "");
+
+    ws.write("data/1/main.txt", &example(1));
+    let stdout = assert_success(&ws.aoc(&["run", "2022", "1", "--save"]));
+    assert!(stdout.contains("Saved to data/1/main.answers.json"));
+    let answers = fs::read_to_string(ws.path("data/1/main.answers.json")).unwrap();
+    assert!(answers.contains(r#""1": "24000""#));
+    let attempts = fs::read_to_string(ws.path(".aoc-cache/attempts.jsonl")).unwrap();
+    assert_eq!(attempts.lines().count(), 2);
+    assert!(attempts.contains(r#""label":"main""#));
+}
+
+#[test]
+fn test_run_all_variants() {
+    let ws = Workspace::new();
+    ws.write("example.txt", &example(6));
+
+    let args = ["run", "2022", "6", "-i", "example.txt", "--all-variants"];
+    let stdout = assert_success(&ws.aoc(&args));
+    assert!(stdout.contains("  Part 1 [main]: 7 ("));
+    assert!(stdout.contains("  Part 1 [last-seen]: 7 ("));
+    assert!(stdout.contains("  Part 2 [last-seen]: 19 ("));
+
+    ws.write("example.txt", &example(14));
+    let args = ["run", "2022", "14", "-i", "example.txt", "--all-variants"];
+    let stdout = assert_success(&ws.aoc(&args));
+    assert!(stdout.contains("  Part 1 [main]: 24 ("));
+    assert!(stdout.contains("  Part 2 [reference]: 93 ("));
+}
+
+#[test]
+fn test_run_errors() {
+    let ws = Workspace::new();
+    assert_error(&ws.aoc(&["run", "2022", "20"]), "day 20 is not implemented");
+    assert_error(&ws.aoc(&["run", "2015", "1"]), "no solutions for year 2015");
+    assert_error(
+        &ws.aoc(&["run", "2022", "1", "-i", "missing.txt"]),
+        "reading missing.txt",
+    );
+    assert_error(
+        &ws.aoc(&["run", "2022", "1", "-l", "alice"]),
+        "has no input labeled alice",
+    );
+    assert_error(
+        &ws.aoc(&["run", "2022", "1", "--plugin", "missing.so"]),
+        "missing.so",
+    );
+
+    for args in [
+        &["run", "2022", "26"][..],
+        &["run", "2022"],
+        &["frobnicate"],
+        &[],
+    ] {
+        assert_eq!(ws.aoc(args).status.code(), Some(2), "{args:?}");
+    }
+
+    ws.write("bad.txt", "jump 3\n");
+    let stdout = assert_success(&ws.aoc(&["run", "2022", "10", "-i", "bad.txt"]));
+    assert!(stdout.contains("Part 1: error: undefined instruction"));
+}
+
+#[test]
+fn test_unknown_day() {
+    let ws = Workspace::new();
+    for args in [
+        &["run", "2022", "20"][..],
+        &["verify", "2022", "20"],
+        &["bench", "2022", "20"],
+        &["sim", "2022", "20", "1"],
+        &["debug", "2022", "20", "1"],
+    ] {
+        assert_error(&ws.aoc(args), "day 20 is not implemented");
+    }
+    for args in [&["run", "2022", "0"][..], &["sim", "2022", "26", "1"]] {
+        let output = ws.aoc(args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(stderr(&output).contains("is not in 1..=25"), "{args:?}");
+    }
+}
+
+#[test]
+fn test_verify() {
+    let ws = Workspace::new();
+    ws.write("data/2/main.txt", &example(2));
+    ws.write("data/2/main.answers.json", r#"{"1": "15", "2": "12"}"#);
+    ws.write("data/2/other.txt", &example(2));
+
+    let summary = assert_success(&ws.aoc(&["verify", "2022", "2", "-l", "main"]));
+    assert!(summary.contains("2 passed, 0 failed, 0 without expected answers"));
+
+    let summary = assert_success(&ws.aoc(&["verify", "2022"]));
+    assert!(summary.contains("2 passed, 0 failed, 2 without expected answers"));
+
+    ws.write("data/2/main.answers.json", r#"{"1": "15", "2": "13"}"#);
+    let output = ws.aoc(&["verify", "2022", "2"]);
+    assert_error(&output, "1 answers are wrong");
+    assert!(stdout(&output).contains(r#"FAILED, expected "13", got "12""#));
+
+    ws.write("data/6/main.txt", &example(6));
+    let summary = assert_success(&ws.aoc(&["verify", "2022", "6", "--all-variants"]));
+    assert!(summary.contains("Day 6 [main] part 2 [last-seen]: ok ("));
+    assert!(summary.contains("2 passed, 0 failed, 2 without expected answers"));
+}
+
+#[test]
+fn test_bench() {
+    let ws = Workspace::new();
+    ws.write("data/4/main.txt", &example(4));
+    let stdout = assert_success(&ws.aoc(&["bench", "2022", "4", "-n", "2"]));
+    assert!(stdout.contains("Day 4 [main]"));
+    assert!(stdout.contains("  Part 2: mean "));
+
+    ws.write("data/6/main.txt", &example(6));
+    let args = ["bench", "2022", "6", "-n", "2", "--all-variants"];
+    let stdout = assert_success(&ws.aoc(&args));
+    assert!(stdout.contains("  Part 1 [main]: mean "));
+    assert!(stdout.contains("x main\n"));
+    assert_eq!(
+        ws.aoc(&["bench", "2022", "4", "-n", "0"]).status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_examples_and_read() {
+    let ws = Workspace::new();
+    assert_error(&ws.aoc(&["read", "2022", "2"]), "not cached");
+
+    ws.write("day2.html", PAGE);
+    let stdout = assert_success(&ws.aoc(&["examples", "import", "day2.html"]));
+    assert!(stdout.contains("Block 1 saved to year2022/test_data/day2.txt"));
+    assert_eq!(
+        fs::read_to_string(ws.path("year2022/test_data/day2.txt")).unwrap(),
+        "A Y\nB X\nC Z\n"
+    );
+    let answers = fs::read_to_string(ws.path("year2022/test_data/day2.answers.json")).unwrap();
+    assert!(answers.contains(r#""1": "15""#));
+    assert_error(
+        &ws.aoc(&["examples", "import", "day2.html"]),
+        "already exists",
+    );
+    assert_error(
+        &ws.aoc(&["examples", "import", "missing.html"]),
+        "reading missing.html",
+    );
+
+    let stdout = assert_success(&ws.aoc(&["read", "2022", "2"]));
+    assert!(stdout.contains("## --- Day 2: Rock Paper Scissors ---"));
+    assert!(stdout.contains("```\nA Y\nB X\nC Z\n```"));
+}
+
+#[test]
+fn test_today() {
+    let ws = Workspace::new();
+    let stdout = assert_success(&ws.aoc(&["today", "--year", "2022"]));
+    assert!(stdout.contains("Advent of Code 2022"));
+    assert!(stdout.contains("25"));
+}
+
+#[test]
+fn test_leaderboard() {
+    let ws = Workspace::new();
+    ws.write("leaderboard.json", LEADERBOARD);
+    let stdout = assert_success(&ws.aoc(&["leaderboard", "stats", "leaderboard.json"]));
+    assert!(stdout.contains("Alice"));
+    let json = assert_success(&ws.aoc(&["leaderboard", "stats", "leaderboard.json", "--json"]));
+    assert!(json.trim_start().starts_with('{'));
+
+    ws.write("empty.json", "");
+    assert_error(
+        &ws.aoc(&["leaderboard", "stats", "empty.json"]),
+        "parsing empty.json",
+    );
+}
+
+#[test]
+fn test_data() {
+    let ws = Workspace::new();
+    ws.write("data/2/main.txt", &example(2));
+    assert_error(&ws.aoc(&["data", "encrypt"]), "no key found");
+
+    let stdout = assert_success(&ws.aoc(&["data", "keygen"]));
+    assert!(stdout.starts_with("Key written to"));
+    assert_error(&ws.aoc(&["data", "keygen"]), "already exists");
+
+    assert_success(&ws.aoc(&["data", "encrypt"]));
+    assert!(!ws.path("data/2/main.txt").exists());
+    assert!(ws.path("data/2/main.txt.age").exists());
+
+    let stdout = assert_success(&ws.aoc(&["run", "2022", "2"]));
+    assert!(stdout.contains("Part 1: 15 ("));
+
+    assert_success(&ws.aoc(&["data", "decrypt", "--keep"]));
+    assert!(ws.path("data/2/main.txt.age").exists());
+    assert_eq!(
+        fs::read_to_string(ws.path("data/2/main.txt")).unwrap(),
+        example(2)
+    );
+    assert_error(
+        &ws.aoc(&["data", "decrypt", "data/2/main.txt"]),
+        "not an encrypted file",
+    );
+}
+
+#[test]
+fn test_gen() {
+    let ws = Workspace::new();
+    let args = ["gen", "2022", "13", "--size", "20", "--seed", "42"];
+    let input = assert_success(&ws.aoc(&args));
+    assert_eq!(input, assert_success(&ws.aoc(&args)));
+    assert_eq!(input.split("\n\n").count(), 20);
+
+    assert_success(&ws.aoc(&[&args[..], &["-o", "gen.txt"]].concat()));
+    assert_eq!(fs::read_to_string(ws.path("gen.txt")).unwrap(), input);
+    assert_success(&ws.aoc(&["run", "2022", "13", "-i", "gen.txt"]));
+
+    let output = ws.aoc(&["gen", "2022", "13"]);
+    assert_success(&output);
+    assert!(stderr(&output).starts_with("seed "));
+}
+
+#[test]
+fn test_difftest() {
+    let ws = Workspace::new();
+    let stdout = assert_success(&ws.aoc(&["difftest", "2022", "8", "--cases", "5"]));
+    assert_eq!(stdout, "Day 8 part 1: 5 inputs agree\n");
+    assert_error(
+        &ws.aoc(&["difftest", "2022", "1"]),
+        "no part has a reference implementation",
+    );
+}
+
+#[test]
+fn test_check_determinism() {
+    let ws = Workspace::new();
+    ws.write("data/7/main.txt", &example(7));
+    let args = ["check-determinism", "2022", "7", "-n", "3"];
+    let stdout = assert_success(&ws.aoc(&args));
+    assert!(stdout.contains("Day 7 [main] part 1: 3 runs agree\n"));
+    assert!(stdout.contains("Day 7 [generated] part 2: 3 runs agree\n"));
+
+    let args = [
+        "check-determinism",
+        "2022",
+        "6",
+        "--all-variants",
+        "-n",
+        "2",
+    ];
+    let stdout = assert_success(&ws.aoc(&args));
+    assert!(stdout.contains("Day 6 [generated] part 2 [last-seen]: 2 runs agree\n"));
+    assert_eq!(
+        ws.aoc(&["check-determinism", "2022", "1", "-n", "1"])
+            .status
+            .code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_inspect() {
+    let ws = Workspace::new();
+    ws.write("day8.txt", &example(8));
+    ws.write("pasted.txt", "1000\r\n2000 \r\n\r\n3000");
+
+    let stdout = assert_success(&ws.aoc(&["inspect", "day8.txt", "pasted.txt"]));
+    assert!(stdout.starts_with("day8.txt\n  lines: 5 in 1 blocks\n"));
+    assert!(stdout.contains("  grid: 5 rows x 5 columns\n"));
+    assert!(stdout.contains("\npasted.txt\n  lines: 4 in 2 blocks\n"));
+    assert!(stdout.contains("  numbers: 3, from 1000 to 3000\n"));
+    assert!(stdout.contains("  warning: 3 lines end with CRLF\n"));
+    assert!(stdout.contains("  warning: 1 lines end with whitespace, first on line 2\n"));
+    assert!(stdout.contains("  warning: the last line doesn't end with a newline\n"));
+    assert_error(&ws.aoc(&["inspect", "missing.txt"]), "reading missing.txt");
+}
+
+#[test]
+fn test_shrink() {
+    let ws = Workspace::new();
+    let args = [
+        "gen", "2022", "8", "--size", "30", "--seed", "3", "-o", "in.txt",
+    ];
+    assert_success(&ws.aoc(&args));
+    assert_error(
+        &ws.aoc(&["shrink", "2022", "8", "2", "-i", "in.txt"]),
+        "day 8 part 2 doesn't fail on in.txt",
+    );
+    // Every run is killed at once, so the part counts as hanging on any input
+    let args = [
+        "shrink",
+        "2022",
+        "8",
+        "2",
+        "-i",
+        "in.txt",
+        "--timeout",
+        "0",
+        "--name",
+        "hangs",
+    ];
+    let stdout = assert_success(&ws.aoc(&args));
+    assert!(stdout.contains("Saved to year2022/test_data/regressions/day8-hangs.txt"));
+    assert!(!ws
+        .path("year2022/test_data/regressions/day8-hangs.answers.json")
+        .exists());
+
+    // With only a few sensors the beacon could be in many places, and part 2 and
+    // its reference pick different ones
+    ws.write(
+        "in.txt",
+        &example(15).lines().take(3).collect::<Vec<_>>().join("\n"),
+    );
+    let args = [
+        "shrink", "2022", "15", "2", "-i", "in.txt", "--name", "gaps",
+    ];
+    let stdout = assert_success(&ws.aoc(&args));
+    assert!(stdout.contains("Saved to year2022/test_data/regressions/day15-gaps.txt"));
+
+    let shrunk = fs::read_to_string(ws.path("year2022/test_data/regressions/day15-gaps.txt"));
+    assert_eq!(shrunk.unwrap().lines().count(), 1);
+    let output = ws.aoc(&[
+        "run",
+        "2022",
+        "15",
+        "-i",
+        "year2022/test_data/regressions/day15-gaps.txt",
+        "--all-variants",
+    ]);
+    assert!(!output.status.success());
+    assert_error(&ws.aoc(&args), "already exists");
+}
+
+#[test]
+fn test_snapshots() {
+    let ws = Workspace::new();
+    let dir = "year2022/test_data/snapshots";
+    ws.write(&format!("{dir}/cave.snap"), "#..\n.#.\n");
+    ws.write(&format!("{dir}/cave.snap.new"), "#..\n.##\n");
+    ws.write(&format!("{dir}/screen.snap.new"), "##\n");
+
+    let stdout = assert_success(&ws.aoc(&["snapshots", "review", "2022"]));
+    assert_eq!(
+        stdout,
+        "--- cave\n  #..\n- .#.\n+ .##\n--- screen (new)\n+ ##\n"
+    );
+    assert_error(
+        &ws.aoc(&["snapshots", "accept", "2022", "missing"]),
+        "snapshot missing has no pending output",
+    );
+
+    assert_success(&ws.aoc(&["snapshots", "accept", "2022", "cave"]));
+    let cave = fs::read_to_string(ws.path(&format!("{dir}/cave.snap"))).unwrap();
+    assert_eq!(cave, "#..\n.##\n");
+    assert_success(&ws.aoc(&["snapshots", "reject", "2022"]));
+    assert!(!ws.path(&format!("{dir}/screen.snap.new")).exists());
+    let stdout = assert_success(&ws.aoc(&["snapshots", "review", "2022"]));
+    assert_eq!(stdout, "No pending snapshots\n");
+}
+
+#[test]
+fn test_sim() {
+    let ws = Workspace::new();
+    ws.write("year2022/test_data/day9.txt", &example(9));
+
+    let stdout = assert_success(&ws.aoc(&["sim", "2022", "9", "1", "-n", "4"]));
+    assert_eq!(stdout, "s..TH\nStopped after 4 steps\n");
+    let stdout = assert_success(&ws.aoc(&["sim", "2022", "9", "1", "--record", "trace.txt"]));
+    assert!(stdout.ends_with(".TH\n...\ns..\nDone after 24 steps\nTrace written to trace.txt\n"));
+
+    let trace = fs::read_to_string(ws.path("trace.txt")).unwrap();
+    assert_eq!(trace.lines().count(), 25);
+    assert!(trace.starts_with("0 0 0,0 0,0\n0 1 1,0 0,0\n"));
+    let stdout = assert_success(&ws.aoc(&["sim", "2022", "9", "1", "--replay", "trace.txt"]));
+    assert!(stdout.starts_with("Step 0\nH\nStep 1\nTH\n"));
+    assert!(stdout.ends_with("Step 24\n.TH\n...\ns..\n"));
+
+    assert_error(
+        &ws.aoc(&["sim", "2022", "10", "1"]),
+        "day 10 has no simulation",
+    );
+    assert_error(&ws.aoc(&["sim", "2022", "9", "3"]), "day 9 has no part 3");
+}
+
+#[test]
+fn test_debug() {
+    let ws = Workspace::new();
+    ws.write("year2022/test_data/day9.txt", &example(9));
+    let mut debugger = ws
+        .command(&["debug", "2022", "9", "1"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let commands = "n 4\n\np 2\nb tail_y >= 3\nc\nh\ng 99\nq\nn\n";
+    let mut stdin = debugger.stdin.take().unwrap();
+    stdin.write_all(commands.as_bytes()).unwrap();
+    drop(stdin);
+    let stdout = assert_success(&debugger.wait_with_output().unwrap());
+
+    // Each piece has the values of a screen and the drawing of the next one
+    let screens: Vec<&str> = stdout.split("step=").collect();
+    assert_eq!(screens.len(), 9);
+    assert_eq!(screens[0], "H\n");
+    assert_eq!(screens[1], "0 x=0 y=0 tail_x=0 tail_y=0 motion=0\ns..TH\n");
+    assert!(screens[2].starts_with("4 x=4 y=0 tail_x=3 tail_y=0 motion=1\n"));
+    assert!(screens[3].starts_with("8 x=4 y=4 "));
+    assert!(screens[4].starts_with("6 x=4 y=2 "));
+    assert!(screens[5].contains("breakpoints: tail_y >= 3\nBreakpoint tail_y >= 3\n"));
+    assert!(screens[6].starts_with("8 x=4 y=4 tail_x=4 tail_y=3 "));
+    assert!(screens[6].contains("Breakpoint tail_y >= 3 hit\n"));
+    assert!(screens[7].contains("\nc            continue until a breakpoint or the end\n"));
+    assert!(screens[8].starts_with("24 x=2 y=2 "));
+    assert!(stdout.ends_with("Error: the simulation ends at step 24\n"));
+}
+
+#[test]
+fn test_completions_and_man() {
+    let ws = Workspace::new();
+    let stdout = assert_success(&ws.aoc(&["completions", "bash"]));
+    assert!(stdout.contains("_clap_complete_aoc()"));
+    assert_eq!(ws.aoc(&["completions", "tcsh"]).status.code(), Some(2));
+
+    // Labels are looked up in the data directory when completing
+    ws.write("data/1/main.txt", "");
+    ws.write("data/14/alice.txt", "");
+    let complete = |args: &[&str]| {
+        let output = ws
+            .command(&[&["--", "aoc"], args].concat())
+            .env("COMPLETE", "fish")
+            .output()
+            .unwrap();
+        assert_success(&output)
+    };
+    let stdout = complete(&["run", "2022", "1", "--label", ""]);
+    assert_eq!(stdout, "alice\tday 14\nmain\tday 1\n");
+    ws.write("data/14/main.txt", "");
+    let stdout = complete(&["run", "2022", "1", "--label", "m"]);
+    assert_eq!(stdout, "main\tday 1, 14\n");
+    let stdout = complete(&["run", "2022", "1"]);
+    assert!(stdout.starts_with("1\tyear 2022\n10\tyear 2022\n"));
+
+    let stdout = assert_success(&ws.aoc(&["man"]));
+    assert!(stdout.contains(".TH aoc 1"));
+    assert_success(&ws.aoc(&["man", "-o", "man"]));
+    assert!(ws.path("man/aoc-run.1").exists());
+}
+
+#[test]
+fn test_serve() {
+    let ws = Workspace::new();
+    let mut server = ws.spawn(&["serve", "--port", "0"]);
+    let mut lines = BufReader::new(server.stdout.take().unwrap()).lines();
+    let line = lines.next().unwrap().unwrap();
+    let address = line
+        .strip_prefix("Listening on http://")
+        .unwrap()
+        .to_owned();
+
+    let request = |request: &str| {
+        let mut stream = TcpStream::connect(&address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+    let days = request("GET /days HTTP/1.1\r\nConnection: close\r\n\r\n");
+    let body = "A Y\nB X\nC Z\n";
+    let answer = request(&format!(
+        "POST /2022/2/1 HTTP/1.1\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    ));
+    let missing =
+        request("POST /2022/20/1 HTTP/1.1\r\nConnection: close\r\nContent-Length: 0\r\n\r\n");
+    server.kill().unwrap();
+    server.wait().unwrap();
+
+    assert!(days.starts_with("HTTP/1.1 200"));
+    assert!(days.contains(r#"{"day":15,"parts":2,"year":2022}"#));
+    assert!(answer.starts_with("HTTP/1.1 200"));
+    assert!(answer.contains(r#""answer":"15""#));
+    assert!(missing.starts_with("HTTP/1.1 404"));
+}
+
+#[test]
+fn test_watch() {
+    let ws = Workspace::new();
+    ws.write("data/2/main.txt", &example(2));
+    ws.write("plugin.so", "not a library");
+    let mut watch = ws.spawn(&[
+        "watch",
+        "2022",
+        "2",
+        "--plugin",
+        "plugin.so",
+        "--poll",
+        "10",
+    ]);
+    let lines: Vec<String> = BufReader::new(watch.stdout.take().unwrap())
+        .lines()
+        .take(3)
+        .map(Result::unwrap)
+        .collect();
+    watch.kill().unwrap();
+    watch.wait().unwrap();
+
+    assert_eq!(lines[0], "Watching plugin.so");
+    assert!(lines[1].starts_with("error: loading plugin.so"));
+    assert_eq!(lines[2], "Waiting for plugin.so to change");
+}
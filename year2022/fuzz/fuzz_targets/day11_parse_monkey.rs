@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day11;
+
+fuzz_target!(|data: &str| {
+    let lines: Vec<&str> = data.lines().map(str::trim).collect();
+    let _ = day11::parse_monkey(&lines);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day13;
+
+fuzz_target!(|data: &str| {
+    let _ = day13::parse_list(data);
+    let _ = day13::parse_nested_list(data, 0);
+});
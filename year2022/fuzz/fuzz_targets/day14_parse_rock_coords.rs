@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day14;
+
+fuzz_target!(|data: &str| {
+    let _ = day14::parse_rock_coords(&aoc::split_puzzle_input(data));
+});
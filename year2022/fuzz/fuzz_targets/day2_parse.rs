@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day2;
+
+fuzz_target!(|data: &str| {
+    let _ = day2::parse(data);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day7;
+
+fuzz_target!(|data: &str| {
+    let lines: Vec<_> = data.split('\n').filter(|e| !e.is_empty()).collect();
+    let _ = day7::build_file_tree(&lines);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day8;
+
+fuzz_target!(|data: &str| {
+    if let Ok(grid) = day8::parse(data) {
+        day8::enumerate_visible_trees(&grid);
+        day8::highest_scenic_score(&grid);
+    }
+});
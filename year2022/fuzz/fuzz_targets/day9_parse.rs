@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use year2022::day9;
+
+fuzz_target!(|data: &str| {
+    let _ = day9::parse(data);
+});
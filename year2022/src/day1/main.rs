@@ -1,20 +1,14 @@
 use anyhow::Result;
-use std::fs;
+use aoc::inputs::Input;
+use aoc::{read_puzzle_data, PuzzleCli};
+use year2022::day1;
 
 fn main() -> Result<()> {
-    let data = fs::read_to_string("data/day1.txt")?;
+    let args = PuzzleCli::new("day1")
+        .with_default_input(&Input::new(1, "main").path)
+        .parse_args();
+    let input = read_puzzle_data(args.input)?;
 
-    let mut elves = data
-        .split("\n\n")
-        .map(|elf| {
-            elf.split('\n')
-                .filter_map(|e| e.parse::<u32>().ok())
-                .sum::<u32>()
-        })
-        .collect::<Vec<_>>();
-    elves.sort_by(|a, b| b.cmp(a));
-
-    let top3_sum = elves.iter().take(3).sum::<u32>();
-    println!("Top 3: {top3_sum}");
+    println!("Top 3: {}", day1::part2(&input)?);
     Ok(())
 }
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use aoc::random::Rng;
+
+pub fn part1(input: &str) -> Result<String> {
+    Ok(top_elves_calories(&parse(input)?, 1).to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    Ok(top_elves_calories(&parse(input)?, 3).to_string())
+}
+
+/// Returns the calories carried by each elf.
+pub fn parse(input: &str) -> Result<Vec<u32>> {
+    input
+        .split("\n\n")
+        .map(|elf| {
+            elf.split('\n')
+                .filter(|e| !e.is_empty())
+                .map(|e| Ok(e.parse::<u32>()?))
+                .sum()
+        })
+        .collect()
+}
+
+pub fn top_elves_calories(elves: &[u32], count: usize) -> u32 {
+    let mut elves = elves.to_vec();
+    elves.sort_by(|a, b| b.cmp(a));
+
+    elves.iter().take(count).sum::<u32>()
+}
+
+/// Generates `size` elves carrying 1 to 10 snacks each.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let elves: Vec<String> = (0..size.max(3))
+        .map(|_| {
+            let snacks: Vec<String> = (0..rng.between(1, 10))
+                .map(|_| rng.between(1000, 60_000).to_string())
+                .collect();
+            snacks.join("\n")
+        })
+        .collect();
+    elves.join("\n\n") + "\n"
+}
@@ -0,0 +1,98 @@
+use std::{collections::BTreeMap, iter};
+
+use anyhow::{bail, Result};
+use aoc::random::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let x_reg_history = run_program(&parse(input)?);
+    Ok(signal_strength(&x_reg_history).to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let x_reg_history = run_program(&parse(input)?);
+    Ok(render(&x_reg_history))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>> {
+    input
+        .split('\n')
+        .filter(|s| !s.is_empty())
+        .map(|line| {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match tokens.as_slice() {
+                ["noop"] => Ok(Instruction::Noop),
+                ["addx", value] => Ok(Instruction::Addx(value.parse()?)),
+                _ => bail!("undefined instruction"),
+            }
+        })
+        .collect()
+}
+
+/// Returns the value of the X register during every cycle.
+pub fn run_program(program: &[Instruction]) -> BTreeMap<i32, i32> {
+    let mut current_cycle = 0;
+    let mut x_reg = 1;
+    let mut x_reg_history = BTreeMap::<i32, i32>::new();
+
+    for instruction in program {
+        current_cycle += 1;
+        x_reg_history.insert(current_cycle, x_reg);
+
+        if let Instruction::Addx(value) = instruction {
+            current_cycle += 1;
+            x_reg_history.insert(current_cycle, x_reg);
+            x_reg += value;
+        }
+    }
+    x_reg_history
+}
+
+/// Sums the signal strengths during the 20th cycle and every 40 cycles after it.
+pub fn signal_strength(x_reg_history: &BTreeMap<i32, i32>) -> i32 {
+    iter::successors(Some(20), |e| Some(e + 40))
+        .take_while(|&e| e <= 220)
+        .filter_map(|e| x_reg_history.get_key_value(&e))
+        .map(|(cycle, x_reg)| cycle * x_reg)
+        .sum()
+}
+
+/// Draws the CRT screen, `#` where the sprite was lit.
+pub fn render(x_reg_history: &BTreeMap<i32, i32>) -> String {
+    let mut screen = String::new();
+    for row in 0..6 {
+        for (cycle, x) in x_reg_history.iter().skip(row as usize * 40).take(40) {
+            if (x - 1..=x + 1).contains(&(cycle - 1 - row * 40)) {
+                screen.push('#');
+            } else {
+                screen.push('.');
+            }
+        }
+        screen.push('\n');
+    }
+    screen
+}
+
+/// Generates a program of at least `size` instructions. It runs for at least
+/// the 240 cycles the CRT draws and keeps the sprite on the screen.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let (mut program, mut instructions, mut cycles, mut x_reg) = (String::new(), 0, 0, 1);
+    while instructions < size || cycles < 240 {
+        let value = rng.between(-5, 5);
+        if rng.chance(0.3) || !(0..40).contains(&(x_reg + value)) {
+            program.push_str("noop\n");
+            cycles += 1;
+        } else {
+            program += &format!("addx {value}\n");
+            x_reg += value;
+            cycles += 2;
+        }
+        instructions += 1;
+    }
+    program
+}
@@ -0,0 +1,254 @@
+mod test;
+
+use std::cell::RefCell;
+use std::collections::LinkedList;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use aoc::random::Rng;
+
+use monkey::Monkey;
+
+pub mod monkey;
+
+pub fn part1(input: &str) -> Result<String> {
+    let monkeys = parse(input)?.into_iter().map(|(monkey, _)| monkey);
+    Ok(play_rounds(monkeys.collect(), 20, None)?.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let (monkeys, dividers): (Vec<_>, Vec<_>) = parse(input)?.into_iter().unzip();
+    let gcd = dividers
+        .iter()
+        .try_fold(1u64, |product, &divider| product.checked_mul(divider))
+        .ok_or_else(|| anyhow!("the product of the dividers overflows"))?;
+    Ok(play_rounds(monkeys, 10_000, Some(gcd))?.to_string())
+}
+
+/// Returns every monkey along with the divider of its test.
+pub fn parse(input: &str) -> Result<Vec<(Monkey, u64)>> {
+    let lines: Vec<&str> = input
+        .split('\n')
+        .map(|e| e.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let monkeys: Vec<_> = lines.chunks(6).map(parse_monkey).collect::<Result<_>>()?;
+    // Monkeys throw to each other by their position in the list
+    for (i, (monkey, _)) in monkeys.iter().enumerate() {
+        if monkey.id() != i {
+            bail!("expected monkey {i}, got monkey {}", monkey.id());
+        }
+    }
+    Ok(monkeys)
+}
+
+/// Returns the level of monkey business after `rounds`. Worry levels are
+/// divided by 3 after each inspection, unless `gcd` is given to keep them
+/// small instead.
+pub fn play_rounds(monkeys: Vec<Monkey>, rounds: usize, gcd: Option<u64>) -> Result<usize> {
+    let monkeys: Vec<_> = monkeys.into_iter().map(RefCell::new).collect();
+    let progress = aoc::progress::current();
+    progress.set_total(rounds as u64);
+    for _ in 0..rounds {
+        for monkey in &monkeys {
+            while monkey.borrow().has_items() {
+                let catcher_id = monkey.borrow_mut().inspect_next_item(gcd)?.unwrap();
+                let Some(catcher) = monkeys.get(catcher_id) else {
+                    bail!("No monkey {catcher_id} to throw to");
+                };
+                let item = monkey.borrow_mut().throw_item().unwrap();
+                catcher.borrow_mut().catch_item(item);
+            }
+        }
+        progress.inc();
+    }
+
+    let mut inspection_counts = monkeys
+        .iter()
+        .map(|m| m.borrow().inspections())
+        .collect::<Vec<_>>();
+    inspection_counts.sort_by(|a, b| b.cmp(a));
+    match inspection_counts.as_slice() {
+        [first, second, ..] => Ok(first * second),
+        _ => bail!("Monkey business needs at least 2 monkeys"),
+    }
+}
+
+pub fn parse_monkey(chunk: &[&str]) -> Result<(Monkey, u64)> {
+    let &[id_line, items_line, op_line, test_line, true_line, false_line] = chunk else {
+        bail!("a monkey is described by 6 lines, got {}", chunk.len());
+    };
+    let id = match id_line.split_whitespace().nth(1) {
+        None => bail!("monkey id not found"),
+        Some(s) => usize::from_str(&s.replace(':', ""))?,
+    };
+    let items: LinkedList<u64> = match items_line.strip_prefix("Starting items:") {
+        None => bail!("starting items not found"),
+        Some(s) => s
+            .split(',')
+            .filter_map(|e| u64::from_str(e.trim()).ok())
+            .collect(),
+    };
+    let op_tokens: Vec<&str> = op_line.split_whitespace().collect();
+    let &[_, _, _, _, o, second_operand] = op_tokens.as_slice() else {
+        bail!("error parsing operation");
+    };
+    let second_operand = match second_operand {
+        "old" => None,
+        _ => match u64::from_str(second_operand) {
+            Ok(value) => Some(value),
+            Err(_) => bail!("error parsing second operand"),
+        },
+    };
+    let add = match o {
+        "+" => true,
+        "*" => false,
+        _ => bail!("unknown operation"),
+    };
+    let op = move |x: u64| {
+        let y = match second_operand {
+            None => x,
+            Some(value) => value,
+        };
+
+        match add {
+            true => x.checked_add(y),
+            false => x.checked_mul(y),
+        }
+    };
+    let divider: u64 = last_token(test_line).parse()?;
+    if divider == 0 {
+        bail!("monkey {id} tests divisibility by 0");
+    }
+    let true_monkey_id: usize = last_token(true_line).parse()?;
+    let false_monkey_id: usize = last_token(false_line).parse()?;
+    if true_monkey_id == id || false_monkey_id == id {
+        bail!("monkey {id} throws to itself");
+    }
+    let test = move |x| {
+        if x % divider == 0 {
+            true_monkey_id
+        } else {
+            false_monkey_id
+        }
+    };
+
+    Ok((
+        Monkey::new(id, items, Box::new(op), Box::new(test)),
+        divider,
+    ))
+}
+
+fn last_token(line: &str) -> &str {
+    line.split_whitespace().last().unwrap_or_default()
+}
+
+struct MonkeySpec {
+    items: Vec<u64>,
+    operator: char,
+    operand: Option<u64>,
+    divider: u64,
+    targets: (usize, usize),
+}
+
+impl MonkeySpec {
+    fn operation(&self, old: u64) -> Option<u64> {
+        let operand = self.operand.unwrap_or(old);
+        match self.operator {
+            '+' => old.checked_add(operand),
+            _ => old.checked_mul(operand),
+        }
+    }
+
+    fn target(&self, worry_lvl: u64) -> usize {
+        match worry_lvl % self.divider {
+            0 => self.targets.0,
+            _ => self.targets.1,
+        }
+    }
+}
+
+/// Generates `size` items held by 2 to 8 monkeys. Dividers are distinct
+/// primes, so worry levels stay small in part 2, and items whose worry level
+/// would overflow during the 20 rounds of part 1 are left out.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    loop {
+        let count = (size / 4).clamp(2, 8);
+        let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+        rng.shuffle(&mut primes);
+
+        let mut monkeys: Vec<MonkeySpec> = (0..count)
+            .map(|id| {
+                let (operator, operand) = match rng.below(10) {
+                    0 => ('*', None),
+                    1..=3 => ('*', Some(rng.between(2, 19) as u64)),
+                    _ => ('+', Some(rng.between(1, 8) as u64)),
+                };
+                let mut others: Vec<usize> = (0..count).filter(|&other| other != id).collect();
+                rng.shuffle(&mut others);
+                MonkeySpec {
+                    items: vec![],
+                    operator,
+                    operand,
+                    divider: primes[id],
+                    targets: (others[0], *others.last().expect("at least 2 monkeys")),
+                }
+            })
+            .collect();
+
+        let mut rejected = 0;
+        while monkeys.iter().map(|m| m.items.len()).sum::<usize>() < size.max(1) {
+            let (holder, item) = (rng.below(count), rng.between(1, 99) as u64);
+            if item_fits(&monkeys, holder, item) {
+                monkeys[holder].items.push(item);
+            } else if rejected == 1000 {
+                break;
+            } else {
+                rejected += 1;
+            }
+        }
+        if rejected < 1000 {
+            return monkeys
+                .iter()
+                .enumerate()
+                .map(|(id, m)| format_monkey(id, m))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+}
+
+/// Follows one item through the 20 rounds of part 1, items don't affect each other.
+fn item_fits(monkeys: &[MonkeySpec], mut holder: usize, mut worry_lvl: u64) -> bool {
+    for _ in 0..20 {
+        for (id, monkey) in monkeys.iter().enumerate() {
+            if id != holder {
+                continue;
+            }
+            match monkey.operation(worry_lvl) {
+                Some(lvl) => worry_lvl = lvl / 3,
+                None => return false,
+            }
+            holder = monkey.target(worry_lvl);
+        }
+    }
+    true
+}
+
+fn format_monkey(id: usize, monkey: &MonkeySpec) -> String {
+    let items: Vec<String> = monkey.items.iter().map(u64::to_string).collect();
+    let operand = match monkey.operand {
+        Some(value) => value.to_string(),
+        None => "old".to_string(),
+    };
+    format!(
+        "Monkey {id}:\n  Starting items: {}\n  Operation: new = old {} {operand}\n  \
+         Test: divisible by {}\n    If true: throw to monkey {}\n    \
+         If false: throw to monkey {}\n",
+        items.join(", "),
+        monkey.operator,
+        monkey.divider,
+        monkey.targets.0,
+        monkey.targets.1,
+    )
+}
@@ -1,10 +1,12 @@
 use std::collections::LinkedList;
 
+use anyhow::{anyhow, Result};
+
 pub struct Monkey {
     id: usize,
     inspections: usize,
     items: LinkedList<u64>,
-    op: Box<dyn Fn(u64) -> u64>,
+    op: Box<dyn Fn(u64) -> Option<u64>>,
     test: Box<dyn Fn(u64) -> usize>,
 }
 
@@ -12,7 +14,7 @@ impl Monkey {
     pub fn new(
         id: usize,
         items: LinkedList<u64>,
-        op: Box<dyn Fn(u64) -> u64>,
+        op: Box<dyn Fn(u64) -> Option<u64>>,
         test: Box<dyn Fn(u64) -> usize>,
     ) -> Self {
         Self {
@@ -36,17 +38,20 @@ impl Monkey {
         !self.items.is_empty()
     }
 
-    pub fn inspect_next_item(&mut self, gcd: Option<u64>) -> Option<usize> {
-        let worry_lvl = self.items.front().map(|&item| (self.op)(item));
-        worry_lvl.map(|lvl| {
-            let lvl = match gcd {
-                None => lvl / 3,
-                Some(gcd) => lvl % gcd,
-            };
-            *self.items.front_mut().unwrap() = lvl;
-            self.inspections += 1;
-            (self.test)(lvl)
-        })
+    /// Fails if the operation overflows the worry level.
+    pub fn inspect_next_item(&mut self, gcd: Option<u64>) -> Result<Option<usize>> {
+        let Some(&item) = self.items.front() else {
+            return Ok(None);
+        };
+        let lvl = (self.op)(item)
+            .ok_or_else(|| anyhow!("worry level {item} overflows in monkey {}", self.id))?;
+        let lvl = match gcd {
+            None => lvl / 3,
+            Some(gcd) => lvl % gcd,
+        };
+        *self.items.front_mut().unwrap() = lvl;
+        self.inspections += 1;
+        Ok(Some((self.test)(lvl)))
     }
 
     pub fn throw_item(&mut self) -> Option<u64> {
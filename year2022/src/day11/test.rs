@@ -0,0 +1,77 @@
+#![cfg(test)]
+
+use super::{parse, parse_monkey, part1, part2};
+
+const MONKEY: [&str; 6] = [
+    "Monkey 0:",
+    "Starting items: 79, 98",
+    "Operation: new = old * 19",
+    "Test: divisible by 23",
+    "If true: throw to monkey 2",
+    "If false: throw to monkey 3",
+];
+
+#[test]
+fn test_parse_monkey() {
+    let (monkey, divider) = parse_monkey(&MONKEY).unwrap();
+    assert_eq!((monkey.id(), divider), (0, 23));
+}
+
+#[test]
+fn test_parse_monkey_malformed() {
+    assert!(parse_monkey(&MONKEY[..5]).is_err());
+    assert!(parse_monkey(&[]).is_err());
+
+    let replacements = [
+        (0, "Monkey"),
+        (1, "Items: 1, 2"),
+        (2, "Operation: new = old / 2"),
+        (2, "Operation: new = old *"),
+        (2, "Operation: new = old * x"),
+        (3, "Test: divisible by 0"),
+        (3, "Test:"),
+        (4, ""),
+        (5, "If false: throw to monkey -1"),
+        (4, "If true: throw to monkey 0"),
+    ];
+    for (i, line) in replacements {
+        let mut monkey = MONKEY;
+        monkey[i] = line;
+        assert!(parse_monkey(&monkey).is_err(), "{line:?}");
+    }
+}
+
+#[test]
+fn test_parse_out_of_order() {
+    let mut monkeys = MONKEY.join("\n");
+    monkeys.push_str("\n\n");
+    monkeys.push_str(&MONKEY.join("\n").replace("Monkey 0", "Monkey 4"));
+    let error = parse(&monkeys).err().unwrap();
+    assert_eq!(error.to_string(), "expected monkey 1, got monkey 4");
+}
+
+#[test]
+fn test_overflow() {
+    let monkeys = "\
+Monkey 0:
+  Starting items: 18446744073709551615
+  Operation: new = old + 1
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 1
+  Operation: new = old * 2
+  Test: divisible by 18446744073709551557
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+    let error = part1(monkeys).err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "worry level 18446744073709551615 overflows in monkey 0"
+    );
+    let error = part2(monkeys).err().unwrap();
+    assert_eq!(error.to_string(), "the product of the dividers overflows");
+}
@@ -0,0 +1,169 @@
+use std::collections::LinkedList;
+
+use anyhow::{bail, Result};
+use aoc::random::Rng;
+use aoc::split_puzzle_input;
+use nalgebra::DMatrix;
+
+/// Lowers the steps to every square until they settle instead of searching from every start.
+pub mod reference;
+
+pub type Position = (usize, usize);
+
+pub fn part1(input: &str) -> Result<String> {
+    let grid = parse(input)?;
+    let (start, end) = start_and_end_positions(&grid)?;
+    let Some(steps) = shortest_path(start, end, &grid) else {
+        bail!("No path from S to E");
+    };
+    Ok(steps.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let grid = parse(input)?;
+    let (_, end) = start_and_end_positions(&grid)?;
+    let Some(min_steps) = shortest_path_from_min_elevation(grid, end) else {
+        bail!("No path from elevation a to E");
+    };
+    Ok(min_steps.to_string())
+}
+
+/// Returns the heightmap.
+pub fn parse(input: &str) -> Result<DMatrix<char>> {
+    let lines = split_puzzle_input(input);
+    let Some(ncols) = lines.first().map(|line| line.chars().count()) else {
+        bail!("Empty heightmap");
+    };
+    if lines.iter().any(|line| line.chars().count() != ncols) {
+        bail!("All rows must have {ncols} squares");
+    }
+    let squares: Vec<char> = lines.iter().flat_map(|line| line.chars()).collect();
+    if let Some(c) = squares.iter().find(|c| !matches!(c, 'a'..='z' | 'S' | 'E')) {
+        bail!("Not an elevation: {c}");
+    }
+    Ok(DMatrix::from_row_slice(lines.len(), ncols, &squares))
+}
+
+pub fn shortest_path_from_min_elevation(grid: DMatrix<char>, end: Position) -> Option<i32> {
+    let min_elevation_positions = all_min_elevation_positions(&grid);
+    let steps_vec = min_elevation_positions
+        .into_iter()
+        .filter_map(|start| shortest_path(start, end, &grid))
+        .collect::<Vec<_>>();
+    steps_vec.into_iter().min()
+}
+
+fn all_min_elevation_positions(grid: &DMatrix<char>) -> Vec<Position> {
+    let mut positions = Vec::new();
+    for (nrow, row) in grid.row_iter().enumerate() {
+        for (ncol, &each) in row.iter().enumerate() {
+            if each == 'a' {
+                positions.push((nrow, ncol));
+            }
+        }
+    }
+    positions
+}
+
+pub fn shortest_path(start: Position, end: Position, grid: &DMatrix<char>) -> Option<i32> {
+    let mut queue = LinkedList::from([start]);
+    let mut distance_grid = DMatrix::from_element(grid.nrows(), grid.ncols(), None);
+    distance_grid[start] = Some(0);
+
+    while !queue.is_empty() {
+        let current_pos = queue.pop_front().expect("non empty");
+        let current_dis = distance_grid[current_pos].expect("already calculated");
+        let current_el = char_to_elevation(grid[current_pos]);
+
+        for each in neighbors(current_pos, grid.nrows(), grid.ncols()) {
+            if distance_grid[each].is_some() {
+                continue;
+            }
+
+            if (0..=current_el + 1).contains(&char_to_elevation(grid[each])) {
+                distance_grid[each] = Some(current_dis + 1);
+                queue.push_back(each);
+            }
+        }
+    }
+    distance_grid[end]
+}
+
+fn neighbors(pos: Position, nrows: usize, ncols: usize) -> Vec<Position> {
+    let offsets = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+    let mut neighbors = Vec::new();
+
+    for o in offsets {
+        let neighbor_row = pos.0.checked_add_signed(o.0);
+        let neighbor_col = pos.1.checked_add_signed(o.1);
+        if let Some((col, row)) = neighbor_col.zip(neighbor_row) {
+            if col < ncols && row < nrows {
+                neighbors.push((row, col));
+            }
+        }
+    }
+    neighbors
+}
+
+pub fn start_and_end_positions(grid: &DMatrix<char>) -> Result<(Position, Position)> {
+    let (mut start, mut end) = (None, None);
+    for (nrow, row) in grid.row_iter().enumerate() {
+        for (ncol, each) in row.iter().enumerate() {
+            match each {
+                'S' => start = Some((nrow, ncol)),
+                'E' => end = Some((nrow, ncol)),
+                _ => continue,
+            }
+            let start = match start {
+                Some(s) => s,
+                None => continue,
+            };
+            let end = match end {
+                Some(e) => e,
+                None => continue,
+            };
+            return Ok((start, end));
+        }
+    }
+    bail!("Failed to find S or E symbols");
+}
+
+fn char_to_elevation(c: char) -> u8 {
+    let c = match c {
+        'S' => 'a',
+        'E' => 'z',
+        _ => c,
+    };
+    *(c as u32).to_le_bytes().first().expect("always present")
+}
+
+/// Generates a square heightmap of at least `size` squares, and at least 14
+/// by 14 so a path can climb from `a` to `z`. A path is carved from `S` in the
+/// top left corner to `E` in the bottom right one, the rest is random.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let side = ((size as f64).sqrt().ceil() as usize).max(14);
+    let mut grid: Vec<Vec<u8>> = (0..side)
+        .map(|_| (0..side).map(|_| b'a' + rng.below(26) as u8).collect())
+        .collect();
+
+    let mut path = vec![(0, 0)];
+    let (mut row, mut col) = (0, 0);
+    while (row, col) != (side - 1, side - 1) {
+        match rng.chance(0.5) {
+            true if row < side - 1 => row += 1,
+            _ if col < side - 1 => col += 1,
+            _ => row += 1,
+        }
+        path.push((row, col));
+    }
+    // Climbs at most one step at a time since the path is longer than 26
+    for (i, &(row, col)) in path.iter().enumerate() {
+        grid[row][col] = b'a' + (i * 25 / (path.len() - 1)) as u8;
+    }
+    grid[0][0] = b'S';
+    grid[side - 1][side - 1] = b'E';
+
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).expect("ascii") + "\n")
+        .collect()
+}
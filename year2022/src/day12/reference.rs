@@ -0,0 +1,39 @@
+use anyhow::{bail, Result};
+use nalgebra::DMatrix;
+
+use super::{char_to_elevation, neighbors, parse, start_and_end_positions, Position};
+
+pub fn part2(input: &str) -> Result<String> {
+    let grid = parse(input)?;
+    let (_, end) = start_and_end_positions(&grid)?;
+    let Some(min_steps) = fewest_steps_from_min_elevation(&grid, end) else {
+        bail!("No path from elevation a to E");
+    };
+    Ok(min_steps.to_string())
+}
+
+/// Starts with 0 steps on every square at elevation `a`, and goes over every
+/// step up to one higher until no square can be reached in fewer steps.
+fn fewest_steps_from_min_elevation(grid: &DMatrix<char>, end: Position) -> Option<i32> {
+    let mut steps = grid.map(|c| (c == 'a').then_some(0));
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for row in 0..grid.nrows() {
+            for col in 0..grid.ncols() {
+                let Some(current) = steps[(row, col)] else {
+                    continue;
+                };
+                let elevation = char_to_elevation(grid[(row, col)]);
+                for next in neighbors((row, col), grid.nrows(), grid.ncols()) {
+                    let climbable = char_to_elevation(grid[next]) <= elevation + 1;
+                    if climbable && steps[next].is_none_or(|s| s > current + 1) {
+                        steps[next] = Some(current + 1);
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+    steps[end]
+}
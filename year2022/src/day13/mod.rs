@@ -0,0 +1,169 @@
+mod test;
+
+use self::Item::{List, Number};
+use anyhow::{bail, Result};
+use aoc::random::Rng;
+use aoc::split_puzzle_input;
+use std::cmp::Ordering;
+
+#[derive(PartialEq, Debug)]
+pub enum Item {
+    Number(i32),
+    List(Vec<Item>),
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let indices = indices_of_correctly_ordered_packets(&parse(input)?);
+    Ok(indices.iter().sum::<usize>().to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    Ok(decoder_key(parse(input)?)?.to_string())
+}
+
+/// Returns every packet, the pairs are the consecutive ones.
+pub fn parse(input: &str) -> Result<Vec<Vec<Item>>> {
+    let lines = split_puzzle_input(input);
+    lines.iter().map(|s| parse_list(s)).collect()
+}
+
+/// Sorts the packets along with the divider packets and multiplies the indices
+/// of the dividers.
+pub fn decoder_key(packets: Vec<Vec<Item>>) -> Result<usize> {
+    let mut flagged_packets = Vec::from_iter(packets.into_iter().map(|e| (false, e)));
+    let (div1, div2) = (parse_list("[[2]]")?, parse_list("[[6]]")?);
+    flagged_packets.extend([(true, div1), (true, div2)]);
+    flagged_packets.sort_by(|a, b| lists_ordering(&a.1, &b.1));
+
+    let mut flag_indices = Vec::new();
+    for (i, &(flagged, _)) in flagged_packets.iter().enumerate() {
+        if flagged {
+            flag_indices.push(i + 1);
+        }
+
+        if flag_indices.len() == 2 {
+            break;
+        }
+    }
+
+    Ok(flag_indices[0] * flag_indices[1])
+}
+
+pub fn indices_of_correctly_ordered_packets(packets: &[Vec<Item>]) -> Vec<usize> {
+    let mut indices = Vec::new();
+    for (i, pair) in packets.chunks_exact(2).enumerate() {
+        if lists_ordering(&pair[0], &pair[1]) == Ordering::Less {
+            indices.push(i + 1);
+        }
+    }
+    indices
+}
+
+pub fn lists_ordering(left: &[Item], right: &[Item]) -> Ordering {
+    for (l, r) in left.iter().zip(right) {
+        let ordering = match l {
+            List(l_lst) => match r {
+                List(r_lst) => lists_ordering(l_lst, r_lst),
+                Number(r_num) => lists_ordering(l_lst, &[Number(*r_num)]),
+            },
+            Number(l_num) => match r {
+                List(r_lst) => lists_ordering(&[Number(*l_num)], r_lst),
+                Number(r_num) => l_num.cmp(r_num),
+            },
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    left.len().cmp(&right.len())
+}
+
+pub fn parse_list(s: &str) -> Result<Vec<Item>> {
+    if !s.starts_with('[') {
+        bail!("Malformed string: a list must start with '['")
+    }
+    let (index, items) = parse_nested_list(s, 0)?;
+    if index + 1 < s.len() {
+        bail!(
+            "Malformed string: unexpected characters after the list at {}",
+            index + 1
+        )
+    } else {
+        Ok(items)
+    }
+}
+
+pub fn parse_nested_list(s: &str, index: usize) -> Result<(usize, Vec<Item>)> {
+    let mut items = Vec::new();
+    let mut buf = String::new();
+    let mut i = index + 1;
+    while (index..s.len()).contains(&i) {
+        let ch = char::from(s.as_bytes()[i]);
+        match ch {
+            ',' => match get_previous_char(s, i)? {
+                ']' => {}
+                _ => {
+                    items.push(Number(buf.parse()?));
+                    buf.clear();
+                }
+            },
+            '[' => {
+                if !matches!(get_previous_char(s, i)?, '[' | ',') {
+                    bail!("Malformed string: expected ',' or ']' at {i}");
+                }
+                let (index, list) = parse_nested_list(s, i)?;
+                i = index;
+                items.push(List(list));
+            }
+            ']' => {
+                return match get_previous_char(s, i)? {
+                    '[' | ']' => Ok((i, items)),
+                    _ => {
+                        items.push(Number(buf.parse()?));
+                        Ok((i, items))
+                    }
+                }
+            }
+            _ => match ch.is_ascii_digit() {
+                true if get_previous_char(s, i)? == ']' => {
+                    bail!("Malformed string: expected ',' or ']' at {i}")
+                }
+                true => buf.push(ch),
+                false => bail!("Malformed string: unknown character at {i}"),
+            },
+        }
+        i += 1;
+    }
+    bail!("Malformed string: missing ']'")
+}
+
+fn get_previous_char(s: &str, index: usize) -> Result<char> {
+    let ch = index.checked_sub(1).and_then(|i| s.as_bytes().get(i));
+    match ch {
+        Some(&ch) => Ok(char::from(ch)),
+        None => bail!("Malformed string: unexpected ',' or ']' at {index}"),
+    }
+}
+
+/// Generates `size` pairs of packets, nested at most 4 lists deep.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let pairs: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let left = random_packet(rng, 4);
+            format!("{left}\n{}\n", random_packet(rng, 4))
+        })
+        .collect();
+    pairs.join("\n")
+}
+
+fn random_packet(rng: &mut Rng, depth: usize) -> String {
+    let items: Vec<String> = (0..rng.below(5))
+        .map(|_| match depth > 0 && rng.chance(0.3) {
+            true => random_packet(rng, depth - 1),
+            false => rng.between(0, 10).to_string(),
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
@@ -1,15 +1,20 @@
 #![cfg(test)]
 
-use crate::{indices_of_correctly_ordered_packets, parse_list, Item, List, Number};
+use super::{
+    indices_of_correctly_ordered_packets, lists_ordering, parse_list, parse_nested_list, Item,
+    List, Number,
+};
 use anyhow::Result;
 use aoc::read_puzzle_input;
+use proptest::prelude::*;
+use std::cmp::Ordering;
 
 #[test]
 fn test_part1() -> Result<()> {
     let lines = read_puzzle_input("test_data/day13.txt")?;
     let packets: Vec<Vec<Item>> = lines.iter().filter_map(|s| parse_list(s).ok()).collect();
     let indices = indices_of_correctly_ordered_packets(&packets);
-    assert_eq!(13usize, indices.iter().sum());
+    assert_eq!(13, indices.iter().sum::<usize>());
     Ok(())
 }
 
@@ -58,3 +63,51 @@ fn test_parse_list() {
         parse_list("[1,[2,[3,[4,[5,6,7]]]],8,9]").unwrap()
     );
 }
+
+#[test]
+fn test_parse_malformed() {
+    for s in [
+        "",
+        "]",
+        "[",
+        "[1,2",
+        "[1,,2]",
+        "[,]",
+        "[1]]",
+        "[1]2",
+        "[1[2]]",
+        "[[]1]",
+        "[[1][2]]",
+        "1",
+        "[\u{e9}]",
+        "[99999999999]",
+    ] {
+        assert!(parse_list(s).is_err(), "{s:?}");
+    }
+    assert!(parse_nested_list("", 0).is_err());
+    assert!(parse_nested_list("[[", 1).is_err());
+}
+
+/// Small numbers and short lists, so that equal and nearly equal packets are common.
+fn packet() -> impl Strategy<Value = Vec<Item>> {
+    let item = (0..4).prop_map(Number).prop_recursive(4, 16, 3, |inner| {
+        prop::collection::vec(inner, 0..3).prop_map(List)
+    });
+    prop::collection::vec(item, 0..4)
+}
+
+proptest! {
+    #[test]
+    fn test_lists_ordering_total_order(a in packet(), b in packet(), c in packet()) {
+        prop_assert_eq!(lists_ordering(&a, &a), Ordering::Equal);
+        prop_assert_eq!(lists_ordering(&a, &b), lists_ordering(&b, &a).reverse());
+
+        let (ab, bc, ac) = (lists_ordering(&a, &b), lists_ordering(&b, &c), lists_ordering(&a, &c));
+        if ab != Ordering::Greater && bc != Ordering::Greater {
+            prop_assert_ne!(ac, Ordering::Greater);
+        }
+        if ab == Ordering::Equal && bc == Ordering::Equal {
+            prop_assert_eq!(ac, Ordering::Equal);
+        }
+    }
+}
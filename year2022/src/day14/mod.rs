@@ -0,0 +1,236 @@
+mod test;
+
+use std::fmt::{Display, Formatter};
+
+use anyhow::{bail, Result};
+use nalgebra::DMatrix;
+
+use aoc::random::Rng;
+use aoc::split_puzzle_input;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tile {
+    Air,
+    Sand,
+    Rock,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let ch = match self {
+            Tile::Air => '.',
+            Tile::Rock => '#',
+            Tile::Sand => 'O',
+        };
+        write!(f, "{ch}")
+    }
+}
+
+/// Drops the sand into an unbounded cave instead of a grid around the source.
+pub mod reference;
+/// Pours the sand one move at a time, for `aoc sim`.
+pub mod sim;
+
+pub type Coordinates = (usize, usize);
+
+const SAND_POURING_START: Coordinates = (0, 500);
+
+pub fn part1(input: &str) -> Result<String> {
+    let (rock_coords, max_coords, min_coords) = parse(input)?;
+    let start = pouring_start(&max_coords, &min_coords);
+
+    let mut grid = create_grid(&rock_coords, &max_coords, &min_coords);
+    let nrows = grid.nrows();
+    let sand_poured = pour_sand(&mut grid, start, |sand_co| {
+        sand_co.0 != nrows - 1 && sand_co != start
+    });
+    // Sand only gets to the bottom row on its way to the abyss
+    let into_abyss = grid.row(nrows - 1).iter().any(|t| *t == Tile::Sand);
+    Ok((sand_poured - usize::from(into_abyss)).to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let (rock_coords, max_coords, min_coords) = parse(input)?;
+    let start = pouring_start(&max_coords, &min_coords);
+
+    let mut grid = create_grid_with_floor(&rock_coords, &max_coords, &min_coords);
+    let sand_poured = pour_sand(&mut grid, start, |sand_co| sand_co != start);
+    Ok(sand_poured.to_string())
+}
+
+pub fn create_grid_with_floor(
+    rocks: &[Vec<Coordinates>],
+    max_coords: &Coordinates,
+    min_coords: &Coordinates,
+) -> DMatrix<Tile> {
+    let grid = create_grid(rocks, max_coords, min_coords);
+    let nrows = grid.nrows();
+    grid.insert_row(nrows, Tile::Air)
+        .insert_row(nrows + 1, Tile::Rock)
+}
+
+pub fn pouring_start(max_coords: &Coordinates, min_coords: &Coordinates) -> Coordinates {
+    (
+        SAND_POURING_START.0 - min_coords.0,
+        SAND_POURING_START.1 - min_coords.1 + max_coords.0,
+    )
+}
+
+pub fn pour_sand<F>(grid: &mut DMatrix<Tile>, start: Coordinates, predicate: F) -> usize
+where
+    F: Fn(Coordinates) -> bool,
+{
+    let mut sand_tiles_poured = 0;
+    'pouring_sand: loop {
+        let sand_coords = fall(start, grid);
+
+        sand_tiles_poured += 1;
+        grid[sand_coords] = Tile::Sand;
+
+        if !predicate(sand_coords) {
+            break 'pouring_sand;
+        }
+    }
+    sand_tiles_poured
+}
+
+/// Returns where a unit of sand falling from `coords` comes to rest, or the
+/// bottom row if it falls that far.
+pub fn fall(coords: Coordinates, grid: &DMatrix<Tile>) -> Coordinates {
+    let mut sand_coords = coords;
+    while let Some(next) = next_move(sand_coords, grid) {
+        sand_coords = next;
+    }
+    sand_coords
+}
+
+/// Returns the tile a unit of sand at `coords` moves to next, if it can move.
+pub fn next_move(coords: Coordinates, grid: &DMatrix<Tile>) -> Option<Coordinates> {
+    tiles_underneath(coords, grid)
+        .into_iter()
+        .find(|&tile| grid[tile] == Tile::Air)
+}
+
+/// Draws the cave row by row, the way the puzzle does.
+pub fn render(grid: &DMatrix<Tile>) -> String {
+    let mut cave = String::new();
+    for row in grid.row_iter() {
+        for tile in row.iter() {
+            cave += &tile.to_string();
+        }
+        cave.push('\n');
+    }
+    cave
+}
+
+fn tiles_underneath(coords: Coordinates, grid: &DMatrix<Tile>) -> Vec<Coordinates> {
+    let offsets = [(1, 0), (1, -1), (1, 1)];
+    let mut tiles = Vec::new();
+
+    for o in offsets {
+        let neighbor_row = coords.0.checked_add_signed(o.0);
+        let neighbor_col = coords.1.checked_add_signed(o.1);
+        if let Some((col, row)) = neighbor_col.zip(neighbor_row) {
+            if col < grid.ncols() && row < grid.nrows() {
+                tiles.push((row, col));
+            }
+        }
+    }
+    tiles
+}
+
+pub fn create_grid(
+    rocks: &[Vec<Coordinates>],
+    max_coords: &Coordinates,
+    min_coords: &Coordinates,
+) -> DMatrix<Tile> {
+    let mut grid = DMatrix::from_element(
+        1 + max_coords.0 - min_coords.0,
+        1 + max_coords.1 - min_coords.1 + max_coords.0 * 2,
+        Tile::Air,
+    );
+    for path in rocks {
+        for points in path.windows(2) {
+            let points = [
+                (points[0].0.min(points[1].0), points[0].1.min(points[1].1)),
+                (points[0].0.max(points[1].0), points[0].1.max(points[1].1)),
+            ];
+            for row in points[0].0..=points[1].0 {
+                for col in points[0].1..=points[1].1 {
+                    let each = (row - min_coords.0, col - min_coords.1 + max_coords.0);
+                    grid[each] = Tile::Rock;
+                }
+            }
+        }
+    }
+    grid
+}
+
+/// Returns the rock paths as `(row, col)` points, along with the bottom right
+/// and top left corners of the scan and the sand source.
+pub fn parse(input: &str) -> Result<(Vec<Vec<Coordinates>>, Coordinates, Coordinates)> {
+    parse_rock_coords(&split_puzzle_input(input))
+}
+
+pub fn parse_rock_coords(
+    lines: &[String],
+) -> Result<(Vec<Vec<Coordinates>>, Coordinates, Coordinates)> {
+    let (mut min_coords, mut max_coords) = ((0, usize::MAX), (0, 0));
+    let mut rock_paths: Vec<Vec<Coordinates>> = vec![];
+
+    for (i, line) in lines.iter().enumerate() {
+        rock_paths.push(vec![]);
+        for point in line.split(" -> ") {
+            let Some((x, y)) = point.split_once(',') else {
+                bail!("Malformed point: {point}");
+            };
+            let c = (y.parse::<usize>()?, x.parse::<usize>()?); // Reverse x and y
+            rock_paths[i].push(c);
+
+            if min_coords.1 > c.1 {
+                min_coords.1 = c.1;
+            }
+            if max_coords.0 < c.0 {
+                max_coords.0 = c.0;
+            }
+            if max_coords.1 < c.1 {
+                max_coords.1 = c.1;
+            }
+        }
+    }
+    if rock_paths.is_empty() {
+        bail!("No rock paths");
+    }
+    // Sand spreads one column further than the lowest rock on each side of
+    // the source, on its way to the floor of part 2
+    let source_col = SAND_POURING_START.1;
+    min_coords.1 = min_coords.1.min(source_col - 1);
+    max_coords.1 = max_coords.1.max(source_col + 1);
+    Ok((rock_paths, max_coords, min_coords))
+}
+
+/// Generates `size` rock paths around the sand source. The rocks are deeper
+/// than they are wide, so sand can't pile up to the source in part 1 and
+/// always ends up flowing into the abyss.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let half_width = 3 + (size as f64).sqrt() as i64 * 2;
+    let (left, right) = (500 - half_width, 500 + half_width);
+    let top = 2 * half_width + 2;
+
+    // Spans the whole width so the cave is wide enough for the floor of part 2
+    let bottom = 3 * top;
+    let mut paths = vec![format!("{left},{bottom} -> {right},{bottom}")];
+    for _ in 1..size {
+        let (mut x, mut y) = (rng.between(left, right), rng.between(top, bottom));
+        let mut points = vec![format!("{x},{y}")];
+        for i in 0..rng.between(1, 4) {
+            match i % 2 == 0 {
+                true => x = rng.between(left, right),
+                false => y = rng.between(top, bottom),
+            }
+            points.push(format!("{x},{y}"));
+        }
+        paths.push(points.join(" -> "));
+    }
+    paths.join("\n") + "\n"
+}
@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use super::{parse, Coordinates, SAND_POURING_START};
+
+pub fn part2(input: &str) -> Result<String> {
+    let (rock_coords, max_coords, _) = parse(input)?;
+    Ok(fill_cave(&rock_coords, max_coords.0 + 2).to_string())
+}
+
+/// Drops sand one unit at a time until a unit rests at the source. Only the
+/// floor bounds the cave.
+fn fill_cave(rocks: &[Vec<Coordinates>], floor: usize) -> usize {
+    let mut blocked = HashSet::new();
+    for path in rocks {
+        for (i, &(row, col)) in path.iter().enumerate() {
+            let (next_row, next_col) = path.get(i + 1).copied().unwrap_or((row, col));
+            for row in row.min(next_row)..=row.max(next_row) {
+                for col in col.min(next_col)..=col.max(next_col) {
+                    blocked.insert((row as i64, col as i64));
+                }
+            }
+        }
+    }
+
+    let floor = floor as i64;
+    let source = (SAND_POURING_START.0 as i64, SAND_POURING_START.1 as i64);
+    let mut units = 0;
+    while !blocked.contains(&source) {
+        let mut sand = source;
+        loop {
+            let (row, col) = sand;
+            let below = [(row + 1, col), (row + 1, col - 1), (row + 1, col + 1)];
+            match below
+                .into_iter()
+                .find(|p| p.0 < floor && !blocked.contains(p))
+            {
+                Some(next) => sand = next,
+                None => break,
+            }
+        }
+        blocked.insert(sand);
+        units += 1;
+    }
+    units
+}
@@ -0,0 +1,146 @@
+use anyhow::{bail, Context, Result};
+use aoc::sim::Simulation;
+use nalgebra::DMatrix;
+
+use super::{
+    create_grid, create_grid_with_floor, fall, next_move, parse, pouring_start, render,
+    Coordinates, Tile, SAND_POURING_START,
+};
+
+/// Pours the sand of part 1 or 2 one move at a time. The last row of the
+/// grid is the abyss, sand reaching it falls forever.
+pub struct Pouring {
+    grid: DMatrix<Tile>,
+    start: Coordinates,
+    /// Where the resting units landed, in the order they did.
+    landed: Vec<Coordinates>,
+    falling: Option<Coordinates>,
+}
+
+pub fn load(input: &str, part: u8) -> Result<Box<dyn Simulation>> {
+    let (rock_coords, max_coords, min_coords) = parse(input)?;
+    let start = pouring_start(&max_coords, &min_coords);
+    let grid = match part {
+        1 => create_grid(&rock_coords, &max_coords, &min_coords),
+        2 => create_grid_with_floor(&rock_coords, &max_coords, &min_coords),
+        _ => bail!("no simulation for part {part}"),
+    };
+    Ok(Box::new(Pouring::new(grid, start)))
+}
+
+impl Pouring {
+    pub fn new(grid: DMatrix<Tile>, start: Coordinates) -> Self {
+        Self {
+            grid,
+            start,
+            landed: Vec::new(),
+            falling: None,
+        }
+    }
+
+    /// Units of sand that came to rest so far.
+    pub fn resting(&self) -> usize {
+        self.landed.len()
+    }
+
+    pub fn falling(&self) -> Option<Coordinates> {
+        self.falling
+    }
+}
+
+impl Simulation for Pouring {
+    /// The number of resting units and where the falling one is, as
+    /// `24 8,500` or `24 -`. Units always land in the same places, so
+    /// restoring takes the last ones away or pours more.
+    fn state(&self) -> String {
+        match self.falling {
+            Some((row, col)) => format!("{} {row},{col}", self.resting()),
+            None => format!("{} -", self.resting()),
+        }
+    }
+
+    fn restore(&mut self, state: &str) -> Result<()> {
+        let Some((resting, falling)) = state.split_once(' ') else {
+            bail!("Malformed state: {state}");
+        };
+        let falling = match falling {
+            "-" => None,
+            _ => {
+                let (row, col) = falling.split_once(',').context("Malformed position")?;
+                Some((row.parse()?, col.parse()?))
+            }
+        };
+
+        let resting: usize = resting.parse()?;
+
+        // Pours on copies, so a state that can't be reached changes nothing
+        let (mut grid, mut landed) = (self.grid.clone(), self.landed.clone());
+        while landed.len() > resting {
+            let sand = landed.pop().expect("more than resting");
+            grid[sand] = Tile::Air;
+        }
+        while landed.len() < resting {
+            let sand = fall(self.start, &grid);
+            if grid[self.start] == Tile::Sand || sand.0 == grid.nrows() - 1 {
+                bail!("Only {} units of sand can rest", landed.len());
+            }
+            grid[sand] = Tile::Sand;
+            landed.push(sand);
+        }
+        if let Some(sand) = falling {
+            if grid.get(sand) != Some(&Tile::Air) {
+                bail!("Sand can't be at {sand:?}");
+            }
+        }
+        (self.grid, self.landed, self.falling) = (grid, landed, falling);
+        Ok(())
+    }
+
+    fn step(&mut self) {
+        if self.done() {
+            return;
+        }
+        let Some(sand) = self.falling else {
+            self.falling = Some(self.start);
+            return;
+        };
+        self.falling = next_move(sand, &self.grid);
+        if self.falling.is_none() {
+            self.grid[sand] = Tile::Sand;
+            self.landed.push(sand);
+        }
+    }
+
+    fn done(&self) -> bool {
+        let in_abyss = self
+            .falling
+            .is_some_and(|sand| sand.0 == self.grid.nrows() - 1);
+        in_abyss || self.grid[self.start] == Tile::Sand
+    }
+
+    /// Draws the falling unit as `~`.
+    fn render(&self) -> String {
+        let mut cave = render(&self.grid);
+        if let Some((row, col)) = self.falling {
+            let index = row * (self.grid.ncols() + 1) + col;
+            cave.replace_range(index..index + 1, "~");
+        }
+        cave
+    }
+
+    fn highlighted(&self) -> Vec<(usize, usize)> {
+        self.falling.into_iter().collect()
+    }
+
+    /// `x` and `y` of the falling unit in the coordinates of the scan, and the
+    /// number of resting units.
+    fn values(&self) -> Vec<(&'static str, i64)> {
+        let mut values = vec![("resting", self.resting() as i64)];
+        if let Some((row, col)) = self.falling {
+            let x = SAND_POURING_START.1 + col - self.start.1;
+            let y = SAND_POURING_START.0 + row - self.start.0;
+            values.extend([("x", x as i64), ("y", y as i64)]);
+        }
+        values
+    }
+}
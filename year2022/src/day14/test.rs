@@ -0,0 +1,30 @@
+#![cfg(test)]
+
+use super::parse_rock_coords;
+
+#[test]
+fn test_parse_rock_coords() {
+    let lines = ["498,4 -> 498,6 -> 496,6".to_owned()];
+    let (paths, max_coords, min_coords) = parse_rock_coords(&lines).unwrap();
+    assert_eq!(paths, [[(4, 498), (6, 498), (6, 496)]]);
+    assert_eq!((max_coords, min_coords), ((6, 501), (0, 496)));
+}
+
+#[test]
+fn test_parse_rock_coords_malformed() {
+    for line in ["498", "498,4 -> 498", "498,4 ->", "a,b", "-1,4", "498,4,5"] {
+        assert!(parse_rock_coords(&[line.to_owned()]).is_err(), "{line:?}");
+    }
+    assert!(parse_rock_coords(&[]).is_err());
+}
+
+#[test]
+fn test_parse_rock_coords_around_source() {
+    let lines = ["501,2 -> 510,2".to_owned()];
+    let (_, max_coords, min_coords) = parse_rock_coords(&lines).unwrap();
+    assert_eq!((max_coords, min_coords), ((2, 510), (0, 499)));
+
+    let lines = ["0,0 -> 0,5".to_owned()];
+    let (_, max_coords, min_coords) = parse_rock_coords(&lines).unwrap();
+    assert_eq!((max_coords, min_coords), ((5, 501), (0, 0)));
+}
@@ -0,0 +1,179 @@
+mod test;
+
+use anyhow::{bail, Result};
+use aoc::random::Rng;
+use aoc::split_puzzle_input;
+use regex::Regex;
+use std::collections::HashSet;
+use std::ops::{Add, Sub};
+
+/// Marks the columns of the row in a vector and scans every row of part 2
+/// instead of collecting the columns in a set and walking along the edges of
+/// the sensors.
+pub mod reference;
+
+pub type Coordinates = (i32, i32);
+
+pub const TARGET_ROW: i32 = 2_000_000;
+pub const BOUNDARIES: (i32, i32) = (0, 4_000_000);
+
+pub struct Sensor {
+    pub coords: Coordinates,
+    pub closest_beacon: Coordinates,
+    /// Manhattan distance to the closest beacon.
+    pub distance: u32,
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let sensors = parse(input)?;
+    let cols = cols_without_beacon_in_row(TARGET_ROW, &sensors);
+    Ok(cols.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let sensors = parse(input)?;
+    let pos = find_distress_beacon_position(BOUNDARIES, &sensors)?;
+    Ok(tuning_frequency(pos).to_string())
+}
+
+/// Returns the sensors and their closest beacons.
+pub fn parse(input: &str) -> Result<Vec<Sensor>> {
+    parse_data(&split_puzzle_input(input))
+}
+
+pub fn tuning_frequency(pos: Coordinates) -> i64 {
+    i64::from(pos.0) * 4_000_000 + i64::from(pos.1)
+}
+
+pub fn cols_without_beacon_in_row(row: i32, sensors: &[Sensor]) -> usize {
+    let cols_with_beacons: Vec<i32> = sensors
+        .iter()
+        .filter(|e| e.closest_beacon.1 == row)
+        .map(|e| e.closest_beacon.0)
+        .collect();
+    let mut cols_without_beacons = HashSet::new();
+
+    for sensor in sensors {
+        let target_row_distance = manhattan_distance(sensor.coords, (sensor.coords.0, row));
+
+        if target_row_distance > sensor.distance {
+            continue;
+        }
+
+        let res_distance = (sensor.distance - target_row_distance) as i32;
+        for col in sensor.coords.0 - res_distance..=sensor.coords.0 + res_distance {
+            if !cols_with_beacons.contains(&col) {
+                cols_without_beacons.insert(col);
+            }
+        }
+    }
+
+    cols_without_beacons.len()
+}
+
+pub fn find_distress_beacon_position(
+    boundaries: (i32, i32),
+    sensors: &[Sensor],
+) -> Result<Coordinates> {
+    let progress = aoc::progress::current();
+    progress.set_total(sensors.len() as u64);
+    for (i, s) in sensors.iter().enumerate() {
+        let edge_distance = (s.distance + 1) as i32;
+        let mut coords_to_check = Vec::new();
+        let rows_range = s.coords.1.sub(edge_distance).max(boundaries.0)
+            ..=s.coords.1.add(edge_distance).min(boundaries.1);
+
+        for row in rows_range {
+            let res_distance =
+                edge_distance - manhattan_distance(s.coords, (s.coords.0, row)) as i32;
+            let cols = [s.coords.0 - res_distance, s.coords.0 + res_distance];
+            for col in cols {
+                if col < boundaries.0 || col > boundaries.1 {
+                    continue;
+                }
+                coords_to_check.push((col, row));
+            }
+        }
+
+        'outer: for c in &coords_to_check {
+            for (j, e) in sensors.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                if manhattan_distance(e.coords, *c) <= e.distance {
+                    continue 'outer;
+                }
+            }
+            return Ok(*c);
+        }
+        progress.inc();
+    }
+
+    bail!("Can't find distress beacon");
+}
+
+pub fn parse_data(lines: &[String]) -> Result<Vec<Sensor>> {
+    let re = Regex::new(r"-?\d+")?;
+    let mut sensors = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        let items: Vec<_> = re
+            .find_iter(line)
+            .filter_map(|m| m.as_str().parse::<i32>().ok())
+            .collect();
+
+        if items.len() != 4 {
+            bail!("parsing error on line {i}");
+        }
+
+        let (sensor, beacon) = ((items[0], items[1]), (items[2], items[3]));
+        sensors.push(Sensor {
+            coords: sensor,
+            closest_beacon: beacon,
+            distance: manhattan_distance(sensor, beacon),
+        });
+    }
+    Ok(sensors)
+}
+
+pub fn manhattan_distance(a: Coordinates, b: Coordinates) -> u32 {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+/// Generates `size` sensors, at least 4, leaving exactly one position within
+/// the boundaries uncovered. Every sensor's closest beacon is next to that
+/// position, so it is just out of reach of all of them. The four sensors in
+/// the corners around it cover everything else.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    generate_within(BOUNDARIES, size, rng)
+}
+
+fn generate_within(boundaries: (i32, i32), size: usize, rng: &mut Rng) -> String {
+    let (min, max) = (i64::from(boundaries.0), i64::from(boundaries.1));
+    let gap = (rng.between(min, max), rng.between(min, max));
+    let reach = max - min + 1;
+
+    let mut sensors: Vec<(i64, i64)> = [(-1, -1), (-1, 1), (1, -1), (1, 1)]
+        .iter()
+        .map(|&(dx, dy)| (gap.0 + dx * reach, gap.1 + dy * reach))
+        .collect();
+    while sensors.len() < size {
+        let x = rng.between(min - reach / 4, max + reach / 4);
+        let y = rng.between(min - reach / 4, max + reach / 4);
+        if x != gap.0 && (x - gap.0).abs() + (y - gap.1).abs() > 1 {
+            sensors.push((x, y));
+        }
+    }
+    rng.shuffle(&mut sensors);
+
+    sensors
+        .into_iter()
+        .map(|(x, y)| {
+            let beacon_x = gap.0 + (x - gap.0).signum();
+            format!(
+                "Sensor at x={x}, y={y}: closest beacon is at x={beacon_x}, y={}\n",
+                gap.1
+            )
+        })
+        .collect()
+}
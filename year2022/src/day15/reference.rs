@@ -0,0 +1,67 @@
+use anyhow::{bail, Result};
+
+use super::{
+    manhattan_distance, parse, tuning_frequency, Coordinates, Sensor, BOUNDARIES, TARGET_ROW,
+};
+
+pub fn part1(input: &str) -> Result<String> {
+    let sensors = parse(input)?;
+    let cols = cols_without_beacon_in_row(TARGET_ROW, &sensors);
+    Ok(cols.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let sensors = parse(input)?;
+    let pos = find_distress_beacon_position(BOUNDARIES, &sensors)?;
+    Ok(tuning_frequency(pos).to_string())
+}
+
+pub(super) fn cols_without_beacon_in_row(row: i32, sensors: &[Sensor]) -> usize {
+    let in_range: Vec<(i32, i32)> = sensors
+        .iter()
+        .filter_map(|s| {
+            let res_distance = s.distance.checked_sub(s.coords.1.abs_diff(row))? as i32;
+            Some((s.coords.0 - res_distance, s.coords.0 + res_distance))
+        })
+        .collect();
+    let Some(first_col) = in_range.iter().map(|r| r.0).min() else {
+        return 0;
+    };
+    let last_col = in_range.iter().map(|r| r.1).max().expect("not empty");
+
+    let mut no_beacon = vec![false; (last_col - first_col + 1) as usize];
+    for (start, end) in in_range {
+        for col in start..=end {
+            no_beacon[(col - first_col) as usize] = true;
+        }
+    }
+    for s in sensors.iter().filter(|s| s.closest_beacon.1 == row) {
+        no_beacon[(s.closest_beacon.0 - first_col) as usize] = false;
+    }
+    no_beacon.into_iter().filter(|&c| c).count()
+}
+
+pub(super) fn find_distress_beacon_position(
+    boundaries: (i32, i32),
+    sensors: &[Sensor],
+) -> Result<Coordinates> {
+    let progress = aoc::progress::current();
+    progress.set_total(boundaries.0.abs_diff(boundaries.1) as u64 + 1);
+    for row in boundaries.0..=boundaries.1 {
+        let mut col = boundaries.0;
+        'scan: while col <= boundaries.1 {
+            for s in sensors {
+                let row_distance = s.coords.1.abs_diff(row);
+                if manhattan_distance(s.coords, (col, row)) <= s.distance {
+                    // Skip to the first column this sensor doesn't cover
+                    col = s.coords.0 + (s.distance - row_distance) as i32 + 1;
+                    continue 'scan;
+                }
+            }
+            return Ok((col, row));
+        }
+        progress.inc();
+    }
+
+    bail!("Can't find distress beacon");
+}
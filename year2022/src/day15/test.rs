@@ -1,8 +1,12 @@
 #![cfg(test)]
 
-use crate::{cols_without_beacon_in_row, find_distress_beacon_position, parse_data};
+use super::{
+    cols_without_beacon_in_row, find_distress_beacon_position, generate, generate_within,
+    manhattan_distance, parse_data, reference,
+};
 use anyhow::Result;
-use aoc::read_puzzle_input;
+use aoc::random::Rng;
+use aoc::{read_puzzle_input, split_puzzle_input};
 
 const TARGET_ROW: i32 = 10;
 const BOUNDARIES: (i32, i32) = (0, 20);
@@ -24,3 +28,48 @@ fn part2() -> Result<()> {
     assert_eq!((14, 11), position);
     Ok(())
 }
+
+#[test]
+fn test_generate_leaves_one_gap() -> Result<()> {
+    let input = generate(10, &mut Rng::new(42));
+    let sensors = parse_data(&split_puzzle_input(&input))?;
+    assert_eq!(10, sensors.len());
+
+    // The beacons are on both sides of the gap
+    let beacon = sensors.iter().map(|s| s.closest_beacon).min().unwrap();
+    let gap = (beacon.0 + 1, beacon.1);
+    for sensor in &sensors {
+        assert_eq!(sensor.distance + 1, manhattan_distance(sensor.coords, gap));
+    }
+    let position = find_distress_beacon_position(super::BOUNDARIES, &sensors)?;
+    assert_eq!(gap, position);
+    Ok(())
+}
+
+#[test]
+fn test_reference_agrees() -> Result<()> {
+    let lines = read_puzzle_input("test_data/day15.txt")?;
+    let sensors = parse_data(&lines)?;
+    assert_eq!(
+        26,
+        reference::cols_without_beacon_in_row(TARGET_ROW, &sensors)
+    );
+    let position = reference::find_distress_beacon_position(BOUNDARIES, &sensors)?;
+    assert_eq!((14, 11), position);
+
+    for seed in 0..200 {
+        let input = generate_within(BOUNDARIES, 4 + seed as usize % 10, &mut Rng::new(seed));
+        let sensors = parse_data(&split_puzzle_input(&input))?;
+        assert_eq!(
+            reference::cols_without_beacon_in_row(TARGET_ROW, &sensors),
+            cols_without_beacon_in_row(TARGET_ROW, &sensors),
+            "{input}"
+        );
+        assert_eq!(
+            reference::find_distress_beacon_position(BOUNDARIES, &sensors)?,
+            find_distress_beacon_position(BOUNDARIES, &sensors)?,
+            "{input}"
+        );
+    }
+    Ok(())
+}
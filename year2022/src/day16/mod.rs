@@ -0,0 +1,34 @@
+mod test;
+
+use anyhow::{bail, Result};
+use aoc::split_puzzle_input;
+use regex::Regex;
+
+#[derive(Debug)]
+pub struct Valve {
+    pub name: String,
+    pub flow_rate: usize,
+    pub leads_to: Vec<String>,
+}
+
+/// Returns the valves and the tunnels between them.
+pub fn parse(input: &str) -> Result<Vec<Valve>> {
+    parse_input(&split_puzzle_input(input))
+}
+
+pub fn parse_input(lines: &[String]) -> Result<Vec<Valve>> {
+    let regex = Regex::new(r"Valve (\w+) has flow rate=(\d+); tunnels? leads? to valves? (.+)")?;
+    let mut valves = Vec::<Valve>::new();
+    for line in lines {
+        let Some(captures) = regex.captures(line) else {
+            bail!("Malformed valve: {line}");
+        };
+        let (_, [name, flow_rate, leads_to]) = captures.extract();
+        valves.push(Valve {
+            name: name.to_string(),
+            flow_rate: flow_rate.parse()?,
+            leads_to: leads_to.split(", ").map(str::to_string).collect(),
+        });
+    }
+    Ok(valves)
+}
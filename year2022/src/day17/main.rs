@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use anyhow::{anyhow, bail, Result};
+use aoc::inputs::Input;
 use aoc::{read_puzzle_input, PuzzleCli};
 
 const CHAMBER_WIDTH: usize = 7;
@@ -60,7 +61,9 @@ impl Rock {
 }
 
 fn main() -> Result<()> {
-    let args = PuzzleCli::new("day17").parse_args();
+    let args = PuzzleCli::new("day17")
+        .with_default_input(&Input::new(17, "main").path)
+        .parse_args();
     let lines = read_puzzle_input(args.input)?;
     let mut chars = lines
         .first()
@@ -0,0 +1,167 @@
+use anyhow::{anyhow, bail, Result};
+use aoc::random::Rng;
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Shape {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Shape {
+    pub fn play_with(self, other: Self) -> (u32, u32) {
+        let points = match self {
+            _ if self == other => (3, 3),
+            _ if self == other.stronger() => (6, 0),
+            _ => (0, 6),
+        };
+
+        (points.0 + self as u32, points.1 + other as u32)
+    }
+
+    pub fn from_char(c: char) -> Result<Shape> {
+        let shape = match c {
+            'A' | 'X' => Shape::Rock,
+            'B' | 'Y' => Shape::Paper,
+            'C' | 'Z' => Shape::Scissors,
+            _ => return Err(anyhow!("Invalid character. Expected A, B, C, X, Y or Z")),
+        };
+        Ok(shape)
+    }
+
+    pub fn stronger(self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Paper,
+            Shape::Paper => Shape::Scissors,
+            Shape::Scissors => Shape::Rock,
+        }
+    }
+
+    pub fn weaker(self) -> Shape {
+        match self {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
+}
+
+impl Outcome {
+    pub fn pick_shape(&self, shape: Shape) -> Shape {
+        match self {
+            Outcome::Win => shape.stronger(),
+            Outcome::Loss => shape.weaker(),
+            Outcome::Draw => shape,
+        }
+    }
+
+    pub fn from_char(c: char) -> Result<Outcome> {
+        let outcome = match c {
+            'X' => Outcome::Loss,
+            'Y' => Outcome::Draw,
+            'Z' => Outcome::Win,
+            _ => return Err(anyhow!("Invalid character. Expected X, Y or Z")),
+        };
+        Ok(outcome)
+    }
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    Ok(score_as_shapes(&parse(input)?)?.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    Ok(score_as_outcomes(&parse(input)?)?.to_string())
+}
+
+/// Returns the two columns of every round of the strategy guide.
+pub fn parse(input: &str) -> Result<Vec<(char, char)>> {
+    input
+        .trim()
+        .split('\n')
+        .map(|line| {
+            let mut chars = line.chars();
+            match (chars.next(), chars.nth(1)) {
+                (Some(c1), Some(c2)) => Ok((c1, c2)),
+                _ => bail!("Malformed round: {line}"),
+            }
+        })
+        .collect()
+}
+
+/// Reads the second column as the shape to play.
+pub fn score_as_shapes(guide: &[(char, char)]) -> Result<u32> {
+    guide
+        .iter()
+        .map(|&(c1, c2)| {
+            let (opponents, yours) = (Shape::from_char(c1)?, Shape::from_char(c2)?);
+            Ok(yours.play_with(opponents).0)
+        })
+        .sum()
+}
+
+/// Reads the second column as the outcome the round needs to end with.
+pub fn score_as_outcomes(guide: &[(char, char)]) -> Result<u32> {
+    guide
+        .iter()
+        .map(|&(c1, c2)| {
+            let (opponents, outcome) = (Shape::from_char(c1)?, Outcome::from_char(c2)?);
+            Ok(outcome.pick_shape(opponents).play_with(opponents).0)
+        })
+        .sum()
+}
+
+/// Generates `size` rounds of the strategy guide.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let opponent = rng.choose(&['A', 'B', 'C']);
+            format!("{opponent} {}\n", rng.choose(&['X', 'Y', 'Z']))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Shape::{self, *};
+    use proptest::prelude::*;
+
+    fn shape() -> impl Strategy<Value = Shape> {
+        prop_oneof![Just(Rock), Just(Paper), Just(Scissors)]
+    }
+
+    #[test]
+    fn test_rock() {
+        assert_eq!(Rock.play_with(Rock), (4, 4));
+        assert_eq!(Rock.play_with(Paper), (1, 8));
+        assert_eq!(Rock.play_with(Scissors), (7, 3));
+    }
+
+    #[test]
+    fn test_paper() {
+        assert_eq!(Paper.play_with(Rock), (8, 1));
+        assert_eq!(Paper.play_with(Paper), (5, 5));
+        assert_eq!(Paper.play_with(Scissors), (2, 9));
+    }
+
+    #[test]
+    fn test_scissors() {
+        assert_eq!(Scissors.play_with(Rock), (3, 7));
+        assert_eq!(Scissors.play_with(Paper), (9, 2));
+        assert_eq!(Scissors.play_with(Scissors), (6, 6));
+    }
+
+    proptest! {
+        #[test]
+        fn test_play_with_symmetric(a in shape(), b in shape()) {
+            let (a_points, b_points) = a.play_with(b);
+            prop_assert_eq!(b.play_with(a), (b_points, a_points));
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::{anyhow, bail, Result};
+use aoc::random::Rng;
+use std::collections::HashSet;
+
+pub fn part1(input: &str) -> Result<String> {
+    Ok(misplaced_items_priority(&parse(input)?).to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    Ok(badges_priority(&parse(input)?)?.to_string())
+}
+
+/// Returns the priorities of the items in every rucksack.
+pub fn parse(input: &str) -> Result<Vec<Vec<u32>>> {
+    input
+        .trim()
+        .split('\n')
+        .map(|rucksack| rucksack.chars().map(char_to_priority).collect())
+        .collect()
+}
+
+/// Sums the priorities of the items found in both compartments of a rucksack.
+pub fn misplaced_items_priority(rucksacks: &[Vec<u32>]) -> u32 {
+    rucksacks
+        .iter()
+        .map(|items| {
+            let (c1, c2) = items.split_at(items.len() / 2);
+            let misplaced: HashSet<_> = c1.iter().filter(|p| c2.contains(p)).collect();
+            misplaced.into_iter().sum::<u32>()
+        })
+        .sum()
+}
+
+/// Sums the priorities of the items carried by all three elves of a group.
+pub fn badges_priority(rucksacks: &[Vec<u32>]) -> Result<u32> {
+    rucksacks
+        .chunks(3)
+        .map(|group| {
+            let [elf1, elf2, elf3] = group else {
+                bail!("Incomplete group of elves");
+            };
+            match elf1.iter().find(|p| elf2.contains(p) && elf3.contains(p)) {
+                Some(&badge) => Ok(badge),
+                None => bail!("Group without a badge"),
+            }
+        })
+        .sum()
+}
+
+fn char_to_priority(c: char) -> Result<u32> {
+    match c {
+        'a'..='z' => Ok(c as u32 - 96),
+        'A'..='Z' => Ok(c as u32 - 38),
+        _ => Err(anyhow!("Invalid char. Expected a-z or A-Z.")),
+    }
+}
+
+/// Generates `size` rucksacks, rounded up to whole groups of three. Each
+/// rucksack has exactly one item type in both compartments and each group
+/// shares exactly one badge.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut rucksacks = String::new();
+    for _ in 0..size.div_ceil(3).max(1) {
+        let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut items);
+        let badge = items.pop().expect("52 item types");
+
+        // Every elf packs from its own 17 item types, so only the badge is common
+        for pool in items.chunks(17) {
+            let (shared, first, second) = (pool[0], &pool[1..9], &pool[9..]);
+            let len = rng.below(10);
+            let mut left: Vec<char> = (0..len).map(|_| *rng.choose(first)).collect();
+            left.extend([shared, badge]);
+            let mut right: Vec<char> = (0..=len).map(|_| *rng.choose(second)).collect();
+            right.push(shared);
+
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+            rucksacks.extend(left.into_iter().chain(right));
+            rucksacks.push('\n');
+        }
+    }
+    rucksacks
+}
+
+#[cfg(test)]
+mod day3_tests {
+    use super::char_to_priority;
+
+    #[test]
+    fn test_char_to_priority() {
+        assert_eq!(char_to_priority('p').unwrap_or_default(), 16);
+        assert_eq!(char_to_priority('L').unwrap_or_default(), 38);
+        assert_eq!(char_to_priority('P').unwrap_or_default(), 42);
+        assert_eq!(char_to_priority('v').unwrap_or_default(), 22);
+        assert_eq!(char_to_priority('t').unwrap_or_default(), 20);
+        assert_eq!(char_to_priority('s').unwrap_or_default(), 19);
+
+        // unwrap_or_default call on Err will return 0
+        assert_eq!(char_to_priority('1').unwrap_or_default(), 0);
+        assert_eq!(char_to_priority('#').unwrap_or_default(), 0);
+    }
+}
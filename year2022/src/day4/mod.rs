@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+use anyhow::{bail, Result};
+use aoc::random::Rng;
+
+pub fn part1(input: &str) -> Result<String> {
+    let pairs = parse(input)?;
+    let fully_contains = pairs.iter().filter(|t| do_fully_contain(&t.0, &t.1));
+    Ok(fully_contains.count().to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let pairs = parse(input)?;
+    let overlaps = pairs.iter().filter(|t| do_overlap(&t.0, &t.1));
+    Ok(overlaps.count().to_string())
+}
+
+/// Returns the section assignments of every pair. The ranges include their
+/// end, `2-4` is `2..4`.
+pub fn parse(input: &str) -> Result<Vec<(Range<i32>, Range<i32>)>> {
+    input
+        .trim()
+        .split('\n')
+        .map(|pair| {
+            let Some((a, b)) = pair.split_once(',') else {
+                bail!("Malformed pair: {pair}");
+            };
+            Ok((parse_range(a)?, parse_range(b)?))
+        })
+        .collect()
+}
+
+fn parse_range(s: &str) -> Result<Range<i32>> {
+    let Some((start, end)) = s.split_once('-') else {
+        bail!("Malformed range: {s}");
+    };
+    Ok(start.parse()?..end.parse()?)
+}
+
+pub fn do_fully_contain<T: Ord>(a: &Range<T>, b: &Range<T>) -> bool {
+    a.start >= b.start && a.end <= b.end || a.start <= b.start && a.end >= b.end
+}
+
+pub fn do_overlap(a: &Range<i32>, b: &Range<i32>) -> bool {
+    a.start >= b.start && a.start <= b.end || b.start >= a.start && b.start <= a.end
+}
+
+/// Generates `size` pairs of section assignments.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut assignment = || {
+        let start = rng.between(1, 99);
+        format!("{start}-{}", rng.between(start, 99))
+    };
+    (0..size)
+        .map(|_| {
+            let first = assignment();
+            format!("{first},{}\n", assignment())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod day4_tests {
+    use std::collections::HashSet;
+    use std::ops::Range;
+
+    use proptest::prelude::*;
+
+    use super::{do_fully_contain, do_overlap};
+
+    /// Section ranges are inclusive, `2..4` is the input `2-4`.
+    fn sections(range: &Range<i32>) -> HashSet<i32> {
+        (range.start..=range.end).collect()
+    }
+
+    fn section_range() -> impl Strategy<Value = Range<i32>> {
+        (1..30, 0..10).prop_map(|(start, len)| start..start + len)
+    }
+
+    #[test]
+    fn test_do_overlap() {
+        assert!(do_overlap(&(5..7), &(7..9)));
+        assert!(do_overlap(&(2..8), &(3..7)));
+        assert!(do_overlap(&(6..6), &(4..6)));
+        assert!(do_overlap(&(2..6), &(4..8)));
+
+        assert!(do_overlap(&(3..7), &(2..8)));
+
+        assert!(!do_overlap(&(3..6), &(8..10)));
+    }
+
+    proptest! {
+        #[test]
+        fn test_do_overlap_brute_force(a in section_range(), b in section_range()) {
+            let overlap = !sections(&a).is_disjoint(&sections(&b));
+            prop_assert_eq!(do_overlap(&a, &b), overlap);
+        }
+
+        #[test]
+        fn test_do_fully_contain_brute_force(a in section_range(), b in section_range()) {
+            let (a_sections, b_sections) = (sections(&a), sections(&b));
+            let contain = a_sections.is_subset(&b_sections) || b_sections.is_subset(&a_sections);
+            prop_assert_eq!(do_fully_contain(&a, &b), contain);
+        }
+    }
+}
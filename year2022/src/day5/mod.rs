@@ -0,0 +1,150 @@
+use anyhow::{bail, Result};
+use aoc::random::Rng;
+
+/// Moves crates between stacks like one model of the crane.
+pub type Mover = fn(&mut [Vec<char>], &Move);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Move {
+    pub count: usize,
+    /// Index of the stack, the drawing numbers them from 1.
+    pub from: usize,
+    pub to: usize,
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    let (stacks, moves) = parse(input)?;
+    rearrange(stacks, &moves, mover_9000)
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let (stacks, moves) = parse(input)?;
+    rearrange(stacks, &moves, mover_9001)
+}
+
+/// Returns the stacks of crates, bottom first, and the moves of the procedure.
+pub fn parse(input: &str) -> Result<(Vec<Vec<char>>, Vec<Move>)> {
+    let Some((drawing, procedure)) = input.split_once("\n\n") else {
+        bail!("The drawing and the procedure must be separated by a blank line");
+    };
+    let stacks = parse_drawing(drawing)?;
+    let moves = procedure
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_move(line, stacks.len()))
+        .collect::<Result<_>>()?;
+    Ok((stacks, moves))
+}
+
+fn parse_drawing(drawing: &str) -> Result<Vec<Vec<char>>> {
+    let mut rows = drawing.split('\n').rev();
+    let keys_row = rows.next().unwrap_or_default();
+    for (i, key) in keys_row.split_whitespace().enumerate() {
+        if key != (i + 1).to_string() {
+            bail!("Stacks must be numbered from 1, got {key}");
+        }
+    }
+
+    let mut stacks = vec![Vec::new(); keys_row.split_whitespace().count()];
+    for row in rows {
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            match row.chars().nth(1 + i * 4) {
+                None | Some(' ') => {}
+                Some(ch) => stack.push(ch),
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+fn parse_move(line: &str, stacks: usize) -> Result<Move> {
+    let tokens: Vec<_> = line.split_whitespace().collect();
+    let &["move", count, "from", from, "to", to] = tokens.as_slice() else {
+        bail!("Malformed move: {line}");
+    };
+    let stack = |key: &str| -> Result<usize> {
+        match key.parse::<usize>()? {
+            key @ 1.. if key <= stacks => Ok(key - 1),
+            _ => bail!("No stack {key}: {line}"),
+        }
+    };
+    Ok(Move {
+        count: count.parse()?,
+        from: stack(from)?,
+        to: stack(to)?,
+    })
+}
+
+/// Returns the crates on top of the stacks after the moves.
+pub fn rearrange(mut stacks: Vec<Vec<char>>, moves: &[Move], mover: Mover) -> Result<String> {
+    for each in moves {
+        if stacks[each.from].len() < each.count {
+            bail!(
+                "Can't move {} crates from stack {}",
+                each.count,
+                each.from + 1
+            );
+        }
+        mover(&mut stacks, each);
+    }
+    Ok(stacks.iter().filter_map(|stack| stack.last()).collect())
+}
+
+/// Moves the crates one at a time.
+pub fn mover_9000(stacks: &mut [Vec<char>], each: &Move) {
+    let src = &mut stacks[each.from];
+    let crates: Vec<_> = src.drain((src.len() - each.count)..).rev().collect();
+    stacks[each.to].extend(crates);
+}
+
+/// Moves the crates all at once, so they keep their order.
+pub fn mover_9001(stacks: &mut [Vec<char>], each: &Move) {
+    let src = &mut stacks[each.from];
+    let crates: Vec<_> = src.drain((src.len() - each.count)..).collect();
+    stacks[each.to].extend(crates);
+}
+
+/// Generates a drawing of 3 to 9 stacks and `size` moves. No move empties a
+/// stack, so every stack has a crate on top at the end.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let crate_letter = |rng: &mut Rng| char::from(b'A' + rng.below(26) as u8);
+    let mut stacks: Vec<Vec<char>> = (0..rng.between(3, 9))
+        .map(|_| (0..rng.between(1, 8)).map(|_| crate_letter(rng)).collect())
+        .collect();
+    // With more crates than stacks there is always a stack to move from
+    stacks[0].push(crate_letter(rng));
+
+    let height = stacks
+        .iter()
+        .map(Vec::len)
+        .max()
+        .expect("at least 3 stacks");
+    let mut drawing = String::new();
+    for row in (0..height).rev() {
+        let crates: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(row) {
+                Some(c) => format!("[{c}]"),
+                None => "   ".to_string(),
+            })
+            .collect();
+        drawing += &crates.join(" ");
+        drawing.push('\n');
+    }
+    let keys: Vec<String> = (1..=stacks.len()).map(|key| format!(" {key} ")).collect();
+    drawing += &keys.join(" ");
+    drawing.push_str("\n\n");
+
+    for _ in 0..size.max(1) {
+        let sources: Vec<usize> = (0..stacks.len()).filter(|&i| stacks[i].len() > 1).collect();
+        let src = *rng.choose(&sources);
+        let dst = (src + rng.between(1, stacks.len() as i64 - 1) as usize) % stacks.len();
+        let count = rng.between(1, stacks[src].len() as i64 - 1) as usize;
+
+        let at = stacks[src].len() - count;
+        let moved: Vec<char> = stacks[src].drain(at..).collect();
+        stacks[dst].extend(moved);
+        drawing += &format!("move {count} from {} to {}\n", src + 1, dst + 1);
+    }
+    drawing
+}
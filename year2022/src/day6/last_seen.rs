@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+use super::parse;
+
+pub fn part1(input: &str) -> Result<String> {
+    Ok(marker_end(&parse(input)?, 4)?.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    Ok(marker_end(&parse(input)?, 14)?.to_string())
+}
+
+/// Grows a window of unique chars, moving its start past the previous
+/// occurrence of each char that is already in it.
+fn marker_end(data: &[char], len: usize) -> Result<usize> {
+    let mut last_seen = HashMap::new();
+    let mut start = 0;
+    for (i, c) in data.iter().enumerate() {
+        if let Some(previous) = last_seen.insert(c, i) {
+            start = start.max(previous + 1);
+        }
+        if i + 1 - start == len {
+            return Ok(i + 1);
+        }
+    }
+    bail!("No {len} unique chars in a row")
+}
@@ -1,26 +1,20 @@
 use anyhow::Result;
-use std::collections::HashSet;
-use std::fs;
+use aoc::inputs::Input;
+use aoc::{read_puzzle_data, PuzzleCli};
+use year2022::day6;
 
 fn main() -> Result<()> {
-    let data: Vec<char> = fs::read_to_string("data/day6.txt")?.chars().collect();
+    let args = PuzzleCli::new("day6")
+        .with_default_input(&Input::new(6, "main").path)
+        .parse_args();
+    let input = read_puzzle_data(args.input)?;
 
-    let (chars, seq) = find_sequence_of_unique_chars(&data, 4).unwrap();
+    let data = day6::parse(&input)?;
+
+    let (chars, seq) = day6::marker(&data, 4)?;
     println!("Packet marker: {chars} chars, \"{seq}\"");
 
-    let (chars, seq) = find_sequence_of_unique_chars(&data, 14).unwrap();
+    let (chars, seq) = day6::marker(&data, 14)?;
     println!("Message marker: {chars} chars, \"{seq}\"");
-
     Ok(())
 }
-
-fn find_sequence_of_unique_chars(data: &[char], len: usize) -> Option<(usize, String)> {
-    for (i, each) in data.windows(len).enumerate() {
-        let set: HashSet<_> = each.iter().collect();
-        if set.len() == len {
-            return Some((i + len, String::from_iter(each)));
-        }
-    }
-
-    None
-}
@@ -0,0 +1,55 @@
+use anyhow::{bail, Result};
+use aoc::random::Rng;
+use std::collections::HashSet;
+
+/// Remembers where each char was last seen instead of building a set for every window.
+pub mod last_seen;
+
+pub fn part1(input: &str) -> Result<String> {
+    Ok(marker_end(&parse(input)?, 4)?.to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    Ok(marker_end(&parse(input)?, 14)?.to_string())
+}
+
+pub fn parse(input: &str) -> Result<Vec<char>> {
+    Ok(input.trim_end().chars().collect())
+}
+
+/// Returns how many chars are processed before the first marker of `len` unique chars.
+pub fn marker_end(data: &[char], len: usize) -> Result<usize> {
+    Ok(marker(data, len)?.0)
+}
+
+/// Returns the end of the first marker of `len` unique chars, and the marker.
+pub fn marker(data: &[char], len: usize) -> Result<(usize, String)> {
+    match find_sequence_of_unique_chars(data, len) {
+        Some(marker) => Ok(marker),
+        None => bail!("No {len} unique chars in a row"),
+    }
+}
+
+pub fn find_sequence_of_unique_chars(data: &[char], len: usize) -> Option<(usize, String)> {
+    for (i, each) in data.windows(len).enumerate() {
+        let set: HashSet<_> = each.iter().collect();
+        if set.len() == len {
+            return Some((i + len, String::from_iter(each)));
+        }
+    }
+
+    None
+}
+
+/// Generates a datastream of `size` characters. Its first half is made of
+/// three letters only, so both markers are somewhere in the second half.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(28);
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    let mut stream: String = (0..size / 2).map(|_| *rng.choose(&letters[..3])).collect();
+    stream.extend((0..size - size / 2 - 14).map(|_| *rng.choose(&letters)));
+    rng.shuffle(&mut letters);
+    stream.extend(&letters[..14]);
+    stream.push('\n');
+    stream
+}
@@ -41,7 +41,9 @@ impl Node for Directory {
             return Ok(self);
         }
 
-        let child_name = path.iter().nth(1).expect("should exist");
+        let Some(child_name) = path.iter().nth(1) else {
+            bail!("Directory not found!");
+        };
         let name = self.name().to_owned();
 
         match self.contents.get_mut(child_name) {
@@ -0,0 +1,193 @@
+mod test;
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use aoc::random::Rng;
+
+use self::file_tree::{Directory, File, Node};
+
+pub mod file_tree;
+
+static SMALL_DIR_SIZE: usize = 100_000;
+static DISK_CAPACITY: usize = 70_000_000;
+static SPACE_REQUIRED_FOR_UPDATE: usize = 30_000_000;
+
+pub fn part1(input: &str) -> Result<String> {
+    let (mut root, visited) = parse(input)?;
+    let dir_sizes = directory_sizes(&mut root, visited)?;
+    Ok(small_directories_size(&dir_sizes).to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let (mut root, visited) = parse(input)?;
+    let dir_sizes = directory_sizes(&mut root, visited)?;
+    Ok(smallest_directory_to_delete(root.size(), &dir_sizes)?.to_string())
+}
+
+/// Returns the file tree and the paths of the directories visited with `cd`.
+pub fn parse(input: &str) -> Result<(Directory, HashSet<PathBuf>)> {
+    let lines: Vec<_> = input.split('\n').filter(|e| !e.is_empty()).collect();
+    build_file_tree(&lines)
+}
+
+pub fn directory_sizes(
+    root: &mut Directory,
+    visited: HashSet<PathBuf>,
+) -> Result<HashMap<PathBuf, usize>> {
+    visited
+        .into_iter()
+        .map(|path| {
+            let size = root.get_child_dir(&path)?.size();
+            Ok((path, size))
+        })
+        .collect()
+}
+
+pub fn small_directories_size(dir_sizes: &HashMap<PathBuf, usize>) -> usize {
+    dir_sizes.values().filter(|&e| *e <= SMALL_DIR_SIZE).sum()
+}
+
+/// Returns the size of the smallest directory that frees enough space for the update.
+pub fn smallest_directory_to_delete(
+    root_size: usize,
+    dir_sizes: &HashMap<PathBuf, usize>,
+) -> Result<usize> {
+    let space_to_free = root_size.saturating_sub(DISK_CAPACITY - SPACE_REQUIRED_FOR_UPDATE);
+    match dir_sizes.values().filter(|&e| *e > space_to_free).min() {
+        Some(&size) => Ok(size),
+        None => bail!("No directory frees enough space"),
+    }
+}
+
+pub fn build_file_tree(lines: &[&str]) -> Result<(Directory, HashSet<PathBuf>)> {
+    let mut root = Directory::new(OsString::from("/"), HashMap::new());
+    let mut current_dir = PathBuf::from("");
+    let mut temp_elems = HashMap::<OsString, Box<dyn Node>>::new();
+    let mut visited = HashSet::new();
+
+    for line in lines {
+        let mut tokens = line.split_whitespace();
+        let mut next_token = || {
+            tokens
+                .next()
+                .ok_or_else(|| anyhow!("Incomplete line: {line}"))
+        };
+        match next_token()? {
+            "$" => match next_token()? {
+                "cd" => {
+                    save(&mut root, &current_dir, &mut temp_elems)?;
+                    match next_token()? {
+                        ".." => {
+                            current_dir.pop();
+                        }
+                        dir => current_dir.push(dir),
+                    }
+                    visited.insert(current_dir.clone());
+                }
+                "ls" => {}
+                _ => bail!("Undefined command"),
+            },
+            "dir" => {
+                let name = OsString::from(next_token()?);
+                temp_elems.insert(name.clone(), Box::new(Directory::new(name, HashMap::new())));
+            }
+            size => {
+                let size = size.parse::<usize>()?;
+                let name = OsString::from(next_token()?);
+                temp_elems.insert(name.clone(), Box::new(File::new(name, size)));
+            }
+        }
+    }
+
+    save(&mut root, &current_dir, &mut temp_elems)?;
+    Ok((root, visited))
+}
+
+fn save(
+    root: &mut Directory,
+    current_dir: &Path,
+    temp_elems: &mut HashMap<OsString, Box<dyn Node>>,
+) -> Result<()> {
+    if !temp_elems.is_empty() {
+        root.get_child_dir(current_dir)?
+            .contents()
+            .extend(temp_elems.drain());
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct DirSpec {
+    dirs: Vec<(String, usize)>,
+    files: Vec<(String, usize)>,
+}
+
+/// Generates a terminal output exploring a file system with `size` files and
+/// directories. The disk is always full enough for the update to need space.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut dirs = vec![DirSpec::default()];
+    let mut total_size = 0;
+    for _ in 0..size {
+        let parent = rng.below(dirs.len());
+        let name = unique_name(&dirs[parent], rng);
+        if rng.chance(0.3) {
+            let id = dirs.len();
+            dirs[parent].dirs.push((name, id));
+            dirs.push(DirSpec::default());
+        } else {
+            let file_size = rng.between(1, 300_000) as usize;
+            dirs[parent].files.push((name + ".txt", file_size));
+            total_size += file_size;
+        }
+    }
+
+    let used_space = DISK_CAPACITY - SPACE_REQUIRED_FOR_UPDATE + rng.below(10_000_000) + 1;
+    if let Some(missing) = used_space.checked_sub(total_size) {
+        dirs[0].files.push(("big.dat".to_string(), missing));
+    }
+
+    let mut output = String::from("$ cd /\n");
+    list_dir(&dirs, 0, &mut output, rng);
+    output
+}
+
+fn unique_name(dir: &DirSpec, rng: &mut Rng) -> String {
+    loop {
+        let name: String = (0..rng.between(1, 8))
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        let mut names = dir.dirs.iter().chain(&dir.files).map(|e| &e.0);
+        if !names.any(|e| e.strip_suffix(".txt").unwrap_or(e) == name) {
+            return name;
+        }
+    }
+}
+
+fn list_dir(dirs: &[DirSpec], id: usize, output: &mut String, rng: &mut Rng) {
+    let dir = &dirs[id];
+    let mut entries: Vec<String> = dir
+        .dirs
+        .iter()
+        .map(|(name, _)| format!("dir {name}"))
+        .collect();
+    entries.extend(
+        dir.files
+            .iter()
+            .map(|(name, size)| format!("{size} {name}")),
+    );
+    rng.shuffle(&mut entries);
+
+    output.push_str("$ ls\n");
+    for each in entries {
+        output.push_str(&each);
+        output.push('\n');
+    }
+    for (name, child) in &dir.dirs {
+        output.push_str(&format!("$ cd {name}\n"));
+        list_dir(dirs, *child, output, rng);
+        output.push_str("$ cd ..\n");
+    }
+}
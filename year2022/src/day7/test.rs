@@ -0,0 +1,24 @@
+#![cfg(test)]
+
+use super::{build_file_tree, part1};
+
+#[test]
+fn test_build_file_tree_malformed() {
+    for lines in [
+        &["$"][..],
+        &["$ cd"],
+        &["$ rm -rf /"],
+        &["dir"],
+        &["123"],
+        &["abc def"],
+        &["$ cd a", "12 b"],
+        &["$ cd /", "$ ls", "12 b", "$ cd b", "$ ls", "34 c"],
+    ] {
+        assert!(build_file_tree(lines).is_err(), "{lines:?}");
+    }
+}
+
+#[test]
+fn test_part1_unlisted_directory() {
+    assert!(part1("$ cd /\n$ cd a\n").is_err());
+}
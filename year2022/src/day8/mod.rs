@@ -0,0 +1,104 @@
+use std::cmp::Ordering;
+
+use anyhow::{anyhow, bail, Result};
+use aoc::random::Rng;
+use nalgebra::DMatrix;
+
+/// Looks at every tree the same way, edges included, instead of counting the edges apart.
+pub mod reference;
+
+pub fn part1(input: &str) -> Result<String> {
+    Ok(enumerate_visible_trees(&parse(input)?).to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    Ok(highest_scenic_score(&parse(input)?).to_string())
+}
+
+/// Returns the heights of the trees.
+pub fn parse(input: &str) -> Result<DMatrix<u32>> {
+    let lines: Vec<&str> = input.split('\n').filter(|s| !s.is_empty()).collect();
+    let Some(ncols) = lines.first().map(|line| line.len()) else {
+        bail!("No trees");
+    };
+    if lines.iter().any(|line| line.len() != ncols) {
+        bail!("All rows must have {ncols} trees");
+    }
+    let heights = lines
+        .iter()
+        .flat_map(|line| line.chars())
+        .map(|c| {
+            c.to_digit(10)
+                .ok_or_else(|| anyhow!("Not a tree height: {c}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DMatrix::from_row_slice(lines.len(), ncols, &heights))
+}
+
+pub fn enumerate_visible_trees(grid: &DMatrix<u32>) -> usize {
+    // A single line of trees is all edge, and counting both sides counts it twice
+    if grid.nrows() == 1 || grid.ncols() == 1 {
+        return grid.len();
+    }
+    let mut visible = grid.ncols() * 2 + grid.nrows() * 2 - 4;
+
+    for x in 1..(grid.ncols() - 1) {
+        for y in 1..(grid.nrows() - 1) {
+            let (col, row) = (grid.column(x), grid.row(y));
+            let tree = *grid.index((y, x));
+
+            let up = col.iter().take(y).all(|&t| t < tree);
+            let down = col.iter().skip(y + 1).all(|&t| t < tree);
+            let left = row.iter().take(x).all(|&t| t < tree);
+            let right = row.iter().skip(x + 1).all(|&t| t < tree);
+            visible += usize::from(up || down || left || right);
+        }
+    }
+
+    visible
+}
+
+/// Trees on the edge see nothing in one direction, so their score is 0.
+pub fn highest_scenic_score(grid: &DMatrix<u32>) -> usize {
+    let mut scenic_scores = vec![];
+
+    for x in 1..(grid.ncols() - 1) {
+        for y in 1..(grid.nrows() - 1) {
+            let (col, row) = (grid.column(x), grid.row(y));
+            let tree = *grid.index((y, x));
+
+            let up = look_for_trees_from(tree, col.iter().take(y).rev());
+            let down = look_for_trees_from(tree, col.iter().skip(y + 1));
+            let left = look_for_trees_from(tree, row.iter().take(x).rev());
+            let right = look_for_trees_from(tree, row.iter().skip(x + 1));
+            scenic_scores.push(up * down * left * right);
+        }
+    }
+
+    scenic_scores.into_iter().max().unwrap_or(0)
+}
+
+fn look_for_trees_from<'a>(tree: u32, iter: impl Iterator<Item = &'a u32>) -> usize {
+    let mut trees = 0;
+    for t in iter {
+        match t.cmp(&tree) {
+            Ordering::Less => trees += 1,
+            Ordering::Equal | Ordering::Greater => {
+                trees += 1;
+                break;
+            }
+        }
+    }
+    trees
+}
+
+/// Generates a square grid of at least `size` trees.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let side = ((size as f64).sqrt().ceil() as usize).max(3);
+    let mut grid = String::with_capacity(side * (side + 1));
+    for _ in 0..side {
+        grid.extend((0..side).map(|_| char::from(b'0' + rng.below(10) as u8)));
+        grid.push('\n');
+    }
+    grid
+}
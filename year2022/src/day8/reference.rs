@@ -0,0 +1,26 @@
+use anyhow::Result;
+use nalgebra::DMatrix;
+
+use super::parse;
+
+pub fn part1(input: &str) -> Result<String> {
+    Ok(count_visible_trees(&parse(input)?).to_string())
+}
+
+/// A tree is visible if every tree between it and an edge is shorter, which
+/// holds for no trees at all.
+fn count_visible_trees(grid: &DMatrix<u32>) -> usize {
+    let (nrows, ncols) = grid.shape();
+    let mut visible = 0;
+    for row in 0..nrows {
+        for col in 0..ncols {
+            let shorter = |pos: (usize, usize)| grid[pos] < grid[(row, col)];
+            let up = (0..row).all(|r| shorter((r, col)));
+            let down = (row + 1..nrows).all(|r| shorter((r, col)));
+            let left = (0..col).all(|c| shorter((row, c)));
+            let right = (col + 1..ncols).all(|c| shorter((row, c)));
+            visible += usize::from(up || down || left || right);
+        }
+    }
+    visible
+}
@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use aoc::random::Rng;
+
+/// Moves the rope one step at a time, for `aoc sim`.
+pub mod sim;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Position {
+    x: i32,
+    y: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn distance_from(self, other: Position) -> (i32, i32) {
+        (self.x - other.x, self.y - other.y)
+    }
+
+    pub fn shift(&mut self, x: i32, y: i32) {
+        self.x += x;
+        self.y += y;
+    }
+}
+
+pub fn part1(input: &str) -> Result<String> {
+    Ok(simulate(&parse(input)?, 2).to_string())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    Ok(simulate(&parse(input)?, 10).to_string())
+}
+
+/// Returns the motions of the head, as a one step shift and the number of steps.
+pub fn parse(input: &str) -> Result<Vec<(Position, u32)>> {
+    input
+        .split('\n')
+        .filter(|s| !s.is_empty())
+        .map(|line| {
+            let Some((direction, steps)) = line.split_once(' ') else {
+                bail!("Malformed motion: {line}");
+            };
+            let shift = match direction {
+                "U" => Position::new(0, 1),
+                "D" => Position::new(0, -1),
+                "R" => Position::new(1, 0),
+                "L" => Position::new(-1, 0),
+                _ => bail!("Undefined direction"),
+            };
+            Ok((shift, steps.parse()?))
+        })
+        .collect()
+}
+
+/// Returns how many positions the tail of a rope with `len` knots visits.
+pub fn simulate(motions: &[(Position, u32)], len: usize) -> usize {
+    let mut knots = vec![Position::default(); len];
+    let mut visited = HashSet::<Position>::from_iter(vec![Position::default()]);
+
+    for &(shift, steps) in motions {
+        for _ in 0..steps {
+            move_rope(&mut knots, shift);
+            visited.insert(*knots.last().unwrap());
+        }
+    }
+
+    visited.len()
+}
+
+/// Shifts the head by one step and has every other knot follow the previous one.
+pub fn move_rope(knots: &mut [Position], shift: Position) {
+    knots[0].shift(shift.x, shift.y);
+
+    for i in 1..knots.len() {
+        let prev = knots[i - 1];
+        let current = knots.get_mut(i).unwrap();
+        let shift = pick_knot_shift(prev, *current);
+        current.shift(shift.0, shift.1);
+    }
+}
+
+pub fn pick_knot_shift(head: Position, tail: Position) -> (i32, i32) {
+    let (x, y) = head.distance_from(tail);
+    match (x.abs(), y.abs()) {
+        (2, 0) => (x / 2, 0),
+        (2, 1) => (x / 2, y),
+        (0, 2) => (0, y / 2),
+        (1, 2) => (x, y / 2),
+        (2, 2) => (x / 2, y / 2),
+        _ => (0, 0),
+    }
+}
+
+/// Generates `size` motions of the head.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let direction = rng.choose(&['U', 'D', 'L', 'R']);
+            format!("{direction} {}\n", rng.between(1, 20))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::{pick_knot_shift, Position};
+
+    fn touching(a: Position, b: Position) -> bool {
+        let (x, y) = a.distance_from(b);
+        x.abs() <= 1 && y.abs() <= 1
+    }
+
+    #[test]
+    fn test_tail_shift_calculation() {
+        let tail = Position::default();
+        assert_eq!(pick_knot_shift(Position::new(2, 0), tail), (1, 0));
+        assert_eq!(pick_knot_shift(Position::new(2, 1), tail), (1, 1));
+        assert_eq!(pick_knot_shift(Position::new(2, -1), tail), (1, -1));
+
+        assert_eq!(pick_knot_shift(Position::new(-2, 0), tail), (-1, 0));
+        assert_eq!(pick_knot_shift(Position::new(-2, 1), tail), (-1, 1));
+        assert_eq!(pick_knot_shift(Position::new(-2, -1), tail), (-1, -1));
+
+        assert_eq!(pick_knot_shift(Position::new(0, 2), tail), (0, 1));
+        assert_eq!(pick_knot_shift(Position::new(1, 2), tail), (1, 1));
+        assert_eq!(pick_knot_shift(Position::new(-1, 2), tail), (-1, 1));
+
+        assert_eq!(pick_knot_shift(Position::new(0, -2), tail), (0, -1));
+        assert_eq!(pick_knot_shift(Position::new(1, -2), tail), (1, -1));
+        assert_eq!(pick_knot_shift(Position::new(-1, -2), tail), (-1, -1));
+    }
+
+    proptest! {
+        /// A knot can be at most 2 steps away in both directions after the previous knot moved,
+        /// since the previous knot can move diagonally.
+        #[test]
+        fn test_knots_stay_touching(x in -100..100, y in -100..100, dx in -2..=2, dy in -2..=2) {
+            let tail = Position::new(x, y);
+            let head = Position::new(x + dx, y + dy);
+            let (shift_x, shift_y) = pick_knot_shift(head, tail);
+            prop_assert!(shift_x.abs() <= 1 && shift_y.abs() <= 1);
+
+            let mut moved = tail;
+            moved.shift(shift_x, shift_y);
+            prop_assert!(touching(head, moved), "{head:?} {moved:?}");
+            if touching(head, tail) {
+                prop_assert_eq!(moved, tail);
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use anyhow::{bail, Result};
+use aoc::sim::Simulation;
+
+use super::{move_rope, parse, Position};
+
+/// Moves the head of a rope of 2 knots in part 1 or 10 in part 2, one step of
+/// its motions at a time.
+pub struct Rope {
+    motions: Vec<(Position, u32)>,
+    knots: Vec<Position>,
+    /// Index of the current motion and steps of it done.
+    motion: usize,
+    moved: u32,
+}
+
+pub fn load(input: &str, part: u8) -> Result<Box<dyn Simulation>> {
+    let len = match part {
+        1 => 2,
+        2 => 10,
+        _ => bail!("no simulation for part {part}"),
+    };
+    Ok(Box::new(Rope::new(parse(input)?, len)))
+}
+
+impl Rope {
+    pub fn new(motions: Vec<(Position, u32)>, len: usize) -> Self {
+        let mut rope = Self {
+            motions,
+            knots: vec![Position::default(); len],
+            motion: 0,
+            moved: 0,
+        };
+        rope.skip_finished_motions();
+        rope
+    }
+
+    pub fn knots(&self) -> &[Position] {
+        &self.knots
+    }
+
+    fn skip_finished_motions(&mut self) {
+        while self
+            .motions
+            .get(self.motion)
+            .is_some_and(|&(_, steps)| self.moved == steps)
+        {
+            (self.motion, self.moved) = (self.motion + 1, 0);
+        }
+    }
+
+    /// Returns the smallest and largest `x` and `y` of the knots and the start.
+    fn bounds(&self) -> (i32, i32, i32, i32) {
+        let origin = Position::default();
+        let positions = || self.knots.iter().chain([&origin]);
+        let xs = || positions().map(|p| p.x);
+        let ys = || positions().map(|p| p.y);
+        (
+            xs().min().unwrap_or(0),
+            xs().max().unwrap_or(0),
+            ys().min().unwrap_or(0),
+            ys().max().unwrap_or(0),
+        )
+    }
+
+    /// Names the knot like the puzzle does, `H` for the head and `T` or a
+    /// number for the others.
+    fn knot_name(&self, index: usize) -> char {
+        match index {
+            0 => 'H',
+            _ if self.knots.len() == 2 => 'T',
+            _ => char::from_digit(index as u32, 36).unwrap_or('?'),
+        }
+    }
+}
+
+impl Simulation for Rope {
+    /// The current motion, the steps of it done and the knots from the head,
+    /// as `3 1 2,4 1,3`.
+    fn state(&self) -> String {
+        let knots: Vec<String> = self
+            .knots
+            .iter()
+            .map(|knot| format!("{},{}", knot.x, knot.y))
+            .collect();
+        format!("{} {} {}", self.motion, self.moved, knots.join(" "))
+    }
+
+    fn restore(&mut self, state: &str) -> Result<()> {
+        let fields: Vec<&str> = state.split(' ').collect();
+        let [motion, moved, knots @ ..] = fields.as_slice() else {
+            bail!("Malformed state: {state}");
+        };
+        if knots.len() != self.knots.len() {
+            bail!("Expected {} knots, got {}", self.knots.len(), knots.len());
+        }
+        let (motion, moved) = (motion.parse()?, moved.parse()?);
+        let steps = self.motions.get(motion).map_or(0, |&(_, steps)| steps);
+        if motion > self.motions.len() || moved > steps {
+            bail!("No step {moved} of motion {motion}");
+        }
+
+        let mut parsed = Vec::with_capacity(knots.len());
+        for knot in knots {
+            let Some((x, y)) = knot.split_once(',') else {
+                bail!("Malformed knot: {knot}");
+            };
+            parsed.push(Position::new(x.parse()?, y.parse()?));
+        }
+        (self.motion, self.moved, self.knots) = (motion, moved, parsed);
+        self.skip_finished_motions();
+        Ok(())
+    }
+
+    fn step(&mut self) {
+        let Some(&(shift, _)) = self.motions.get(self.motion) else {
+            return;
+        };
+        move_rope(&mut self.knots, shift);
+        self.moved += 1;
+        self.skip_finished_motions();
+    }
+
+    fn done(&self) -> bool {
+        self.motion == self.motions.len()
+    }
+
+    /// Draws the knots and the start `s` in the smallest area around them,
+    /// up being the top.
+    fn render(&self) -> String {
+        let origin = Position::default();
+        let (min_x, max_x, min_y, max_y) = self.bounds();
+
+        let mut area = String::new();
+        for y in (min_y..=max_y).rev() {
+            for x in min_x..=max_x {
+                let here = Position::new(x, y);
+                area.push(match self.knots.iter().position(|&k| k == here) {
+                    Some(index) => self.knot_name(index),
+                    None if here == origin => 's',
+                    None => '.',
+                });
+            }
+            area.push('\n');
+        }
+        area
+    }
+
+    fn highlighted(&self) -> Vec<(usize, usize)> {
+        let (min_x, _, _, max_y) = self.bounds();
+        let head = self.knots[0];
+        vec![((max_y - head.y) as usize, (head.x - min_x) as usize)]
+    }
+
+    /// Where the head and the tail are, and the index of the current motion.
+    fn values(&self) -> Vec<(&'static str, i64)> {
+        let (head, tail) = (self.knots[0], self.knots[self.knots.len() - 1]);
+        vec![
+            ("x", head.x.into()),
+            ("y", head.y.into()),
+            ("tail_x", tail.x.into()),
+            ("tail_y", tail.y.into()),
+            ("motion", self.motion as i64),
+        ]
+    }
+}
@@ -0,0 +1,56 @@
+use aoc::solution::{Solution, Variant};
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub const YEAR: u16 = 2022;
+
+pub const SOLUTIONS: &[Solution] = &[
+    Solution::new(YEAR, 1, &[day1::part1, day1::part2]).with_generator(day1::generate),
+    Solution::new(YEAR, 2, &[day2::part1, day2::part2]).with_generator(day2::generate),
+    Solution::new(YEAR, 3, &[day3::part1, day3::part2]).with_generator(day3::generate),
+    Solution::new(YEAR, 4, &[day4::part1, day4::part2]).with_generator(day4::generate),
+    Solution::new(YEAR, 5, &[day5::part1, day5::part2]).with_generator(day5::generate),
+    Solution::new(YEAR, 6, &[day6::part1, day6::part2])
+        .with_generator(day6::generate)
+        .with_variants(&[
+            Variant::new("last-seen", 1, day6::last_seen::part1),
+            Variant::new("last-seen", 2, day6::last_seen::part2),
+        ]),
+    Solution::new(YEAR, 7, &[day7::part1, day7::part2]).with_generator(day7::generate),
+    Solution::new(YEAR, 8, &[day8::part1, day8::part2])
+        .with_generator(day8::generate)
+        .with_references(&[Some(day8::reference::part1), None]),
+    Solution::new(YEAR, 9, &[day9::part1, day9::part2])
+        .with_generator(day9::generate)
+        .with_simulation(day9::sim::load),
+    Solution::new(YEAR, 10, &[day10::part1, day10::part2]).with_generator(day10::generate),
+    Solution::new(YEAR, 11, &[day11::part1, day11::part2]).with_generator(day11::generate),
+    Solution::new(YEAR, 12, &[day12::part1, day12::part2])
+        .with_generator(day12::generate)
+        .with_references(&[None, Some(day12::reference::part2)]),
+    Solution::new(YEAR, 13, &[day13::part1, day13::part2]).with_generator(day13::generate),
+    Solution::new(YEAR, 14, &[day14::part1, day14::part2])
+        .with_generator(day14::generate)
+        .with_references(&[None, Some(day14::reference::part2)])
+        .with_simulation(day14::sim::load),
+    Solution::new(YEAR, 15, &[day15::part1, day15::part2])
+        .with_generator(day15::generate)
+        .with_references(&[Some(day15::reference::part1), Some(day15::reference::part2)]),
+];
+
+aoc::export_plugin!(YEAR, SOLUTIONS);
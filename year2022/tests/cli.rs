@@ -0,0 +1,166 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use aoc::inputs::Input;
+
+fn day_bin(day: u8) -> Command {
+    let path = match day {
+        1 => env!("CARGO_BIN_EXE_day1"),
+        2 => env!("CARGO_BIN_EXE_day2"),
+        3 => env!("CARGO_BIN_EXE_day3"),
+        4 => env!("CARGO_BIN_EXE_day4"),
+        5 => env!("CARGO_BIN_EXE_day5"),
+        6 => env!("CARGO_BIN_EXE_day6"),
+        7 => env!("CARGO_BIN_EXE_day7"),
+        8 => env!("CARGO_BIN_EXE_day8"),
+        9 => env!("CARGO_BIN_EXE_day9"),
+        10 => env!("CARGO_BIN_EXE_day10"),
+        11 => env!("CARGO_BIN_EXE_day11"),
+        12 => env!("CARGO_BIN_EXE_day12"),
+        13 => env!("CARGO_BIN_EXE_day13"),
+        14 => env!("CARGO_BIN_EXE_day14"),
+        15 => env!("CARGO_BIN_EXE_day15"),
+        16 => env!("CARGO_BIN_EXE_day16"),
+        17 => env!("CARGO_BIN_EXE_day17"),
+        _ => panic!("no binary for day {day}"),
+    };
+    Command::new(path)
+}
+
+fn example(day: u8) -> Input {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("test_data")
+        .join(format!("day{day}.txt"));
+    Input {
+        day,
+        label: "example".to_owned(),
+        path,
+    }
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// Single line answers must appear as a whole word, so `1` does not match `10 knots`.
+fn contains_answer(stdout: &str, answer: &str) -> bool {
+    match answer.contains('\n') {
+        true => stdout.contains(answer.trim_end()),
+        false => stdout
+            .split(|c: char| c.is_whitespace() || c == ':' || c == ',')
+            .any(|word| word == answer),
+    }
+}
+
+/// Days 15 and 16 have no expected answers yet, so their examples only need to run.
+/// Day 1 only prints the calories of the top 3 elves, which is part 2.
+#[test]
+fn test_examples() {
+    for day in 1..=16 {
+        let input = example(day);
+        let mut expected = input.read_answers().unwrap();
+        if day == 1 {
+            expected.remove(&1);
+        }
+        let output = day_bin(day).arg(&input.path).output().unwrap();
+        assert!(output.status.success(), "day {day}: {}", stderr(&output));
+
+        let stdout = stdout(&output);
+        for (part, answer) in expected {
+            assert!(
+                contains_answer(&stdout, &answer),
+                "day {day} part {part}: expected {answer} in\n{stdout}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_missing_input() {
+    for day in 1..=17 {
+        let output = day_bin(day).arg("missing.txt").output().unwrap();
+        assert_eq!(output.status.code(), Some(1), "day {day}");
+        assert!(stderr(&output).contains("No such file"), "day {day}");
+        assert!(stdout(&output).is_empty(), "day {day}");
+    }
+}
+
+#[test]
+fn test_default_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = day_bin(1).current_dir(dir.path()).output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("No such file"));
+
+    std::fs::create_dir_all(dir.path().join("data/1")).unwrap();
+    std::fs::copy(example(1).path, dir.path().join("data/1/main.txt")).unwrap();
+    let output = day_bin(1).current_dir(dir.path()).output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(contains_answer(&stdout(&output), "45000"));
+}
+
+#[test]
+fn test_usage() {
+    for day in 1..=17 {
+        let output = day_bin(day).arg("--help").output().unwrap();
+        let default = format!("[input]  [default: data/{day}/main.txt]");
+        assert!(stdout(&output).contains(&default), "day {day}");
+    }
+
+    let output = day_bin(1).args(["a.txt", "b.txt"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = day_bin(1).arg("--completions").arg("zsh").output().unwrap();
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("#compdef day1"));
+
+    let output = day_bin(1).arg("--man").output().unwrap();
+    assert!(output.status.success());
+    assert!(stdout(&output).contains(".TH day1 1"));
+}
+
+#[test]
+fn test_malformed_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("input.txt");
+    let cases = [
+        (1, "x\n", "invalid digit found in string"),
+        (2, "A Q\n", "Expected A, B, C, X, Y or Z"),
+        (3, "ab1c\n", "Expected a-z or A-Z"),
+        (4, "2-4,6\n", "Malformed range: 6"),
+        (5, "move 1 from 2 to 1\n", "separated by a blank line"),
+        (6, "abc\n", "No 4 unique chars in a row"),
+        (7, "$ cd /\nx.txt\n", "invalid digit found in string"),
+        (8, "30373\n2x512\n", "Not a tree height: x"),
+        (9, "F 4\n", "Undefined direction"),
+        (10, "jump 3\n", "undefined instruction"),
+        (11, "Monkey 0:\n", "a monkey is described by 6 lines, got 1"),
+        (12, "abc\ndef\n", "Failed to find S or E symbols"),
+        (13, "1,2\n[1]\n", "a list must start with '['"),
+        (13, "[1[2]]\n[1]\n", "expected ',' or ']' at 2"),
+        (13, "[[]1]\n[1]\n", "expected ',' or ']' at 3"),
+        (
+            13,
+            "[1]2\n[1]\n",
+            "unexpected characters after the list at 3",
+        ),
+        (14, "498,4 -> x\n", "Malformed point: x"),
+        (15, "Sensor at x=2\n", "parsing error on line 0"),
+        (16, "Valve AA\n", "Malformed valve: Valve AA"),
+        (17, ">>x<\n", "unexpected char x"),
+    ];
+    for (day, input, error) in cases {
+        std::fs::write(&path, input).unwrap();
+        let output = day_bin(day).arg(&path).output().unwrap();
+        assert_eq!(output.status.code(), Some(1), "day {day}");
+        assert!(
+            stderr(&output).contains(error),
+            "day {day}: expected {error:?} in {}",
+            stderr(&output)
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use std::fs;
+
+use anyhow::Result;
+use year2022::{day12, day13, day14, day15, day5, day9};
+
+fn example(day: u8) -> String {
+    fs::read_to_string(format!("test_data/day{day}.txt")).unwrap()
+}
+
+#[test]
+fn test_day5() -> Result<()> {
+    let (stacks, moves) = day5::parse(&example(5))?;
+    assert_eq!(stacks.len(), 3);
+    assert_eq!(
+        moves[0],
+        day5::Move {
+            count: 1,
+            from: 1,
+            to: 0
+        }
+    );
+    assert_eq!(day5::rearrange(stacks, &moves, day5::mover_9001)?, "MCD");
+    Ok(())
+}
+
+#[test]
+fn test_day9() -> Result<()> {
+    let motions = day9::parse(&example(9))?;
+    assert_eq!(day9::simulate(&motions, 2), 13);
+    assert_eq!(day9::simulate(&motions, 10), 1);
+    Ok(())
+}
+
+#[test]
+fn test_day12() -> Result<()> {
+    let grid = day12::parse(&example(12))?;
+    let (start, end) = day12::start_and_end_positions(&grid)?;
+    assert_eq!(day12::shortest_path(start, end, &grid), Some(31));
+    Ok(())
+}
+
+#[test]
+fn test_day13() -> Result<()> {
+    let packets = day13::parse(&example(13))?;
+    let indices = day13::indices_of_correctly_ordered_packets(&packets);
+    assert_eq!(indices, [1, 2, 4, 6]);
+    assert_eq!(day13::decoder_key(packets)?, 140);
+    Ok(())
+}
+
+#[test]
+fn test_day14() -> Result<()> {
+    let (rocks, max_coords, min_coords) = day14::parse(&example(14))?;
+    let start = day14::pouring_start(&max_coords, &min_coords);
+    let mut grid = day14::create_grid_with_floor(&rocks, &max_coords, &min_coords);
+    assert_eq!(day14::pour_sand(&mut grid, start, |sand| sand != start), 93);
+    Ok(())
+}
+
+#[test]
+fn test_day15() -> Result<()> {
+    let sensors = day15::parse(&example(15))?;
+    assert_eq!(day15::cols_without_beacon_in_row(10, &sensors), 26);
+    let position = day15::find_distress_beacon_position((0, 20), &sensors)?;
+    assert_eq!(day15::tuning_frequency(position), 56_000_011);
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use aoc::random::Rng;
+use year2022::SOLUTIONS;
+
+#[test]
+fn test_generated_inputs_are_solved() {
+    // Day 15 always spans the full 4 million boundaries, it is tested on its own
+    for solution in SOLUTIONS.iter().filter(|s| s.day != 15) {
+        let generator = solution.generator.expect("every day has a generator");
+        for (seed, size) in [(0, 1), (1, 10), (2, 50), (3, 200)] {
+            let input = generator(size, &mut Rng::new(seed));
+            for (i, part) in solution.parts.iter().enumerate() {
+                if let Err(e) = part(&input) {
+                    panic!("day {} part {} failed: {e}\n{input}", solution.day, i + 1);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_same_seed_same_input() {
+    for solution in SOLUTIONS {
+        let generator = solution.generator.expect("every day has a generator");
+        let input = generator(20, &mut Rng::new(42));
+        assert_eq!(
+            input,
+            generator(20, &mut Rng::new(42)),
+            "day {}",
+            solution.day
+        );
+        assert_ne!(
+            input,
+            generator(20, &mut Rng::new(43)),
+            "day {}",
+            solution.day
+        );
+    }
+}
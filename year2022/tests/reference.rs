@@ -0,0 +1,50 @@
+use aoc::difftest::{find_mismatch, outcome};
+use aoc::random::Rng;
+use year2022::SOLUTIONS;
+
+#[test]
+fn test_references_agree() {
+    // Day 15 spreads its sensors over 4 million columns and rows, which both its
+    // parts and its references walk one by one; day15/test.rs compares them
+    // within the smaller boundaries of the example instead
+    for solution in SOLUTIONS.iter().filter(|s| s.day != 15) {
+        for part in 1..=solution.parts.len() as u8 {
+            if solution.reference(part).is_none() {
+                continue;
+            }
+            for size in [10, 50] {
+                if let Some(m) = find_mismatch(solution, part, size, 0..10) {
+                    panic!(
+                        "day {} part {part} disagrees on seed {}: {} instead of {}\n{}",
+                        solution.day, m.seed, m.answer, m.reference, m.input
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_variants_agree() {
+    for solution in SOLUTIONS.iter().filter(|s| !s.variants.is_empty()) {
+        let generator = solution
+            .generator
+            .expect("days with variants have a generator");
+        for part in 1..=solution.parts.len() as u8 {
+            let variants = solution.variants(part);
+            let (_, main) = variants[0];
+            for seed in 0..10 {
+                let input = generator(50, &mut Rng::new(seed));
+                let expected = outcome(main, &input);
+                for &(name, solve) in &variants[1..] {
+                    let answer = outcome(solve, &input);
+                    assert!(
+                        answer.agrees_with(&expected),
+                        "day {} part {part} [{name}] disagrees on seed {seed}: {answer} instead of {expected}",
+                        solution.day
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::Path;
+
+use aoc::difftest::{outcome, Outcome};
+use aoc::inputs::{Input, REGRESSIONS_DIR};
+use year2022::SOLUTIONS;
+
+/// Runs every part and its variants on the inputs saved by `aoc shrink`. They
+/// must not panic and must give the reference answer, if one was saved.
+#[test]
+fn test_regressions() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("test_data")
+        .join(REGRESSIONS_DIR);
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|e| e != "txt") {
+            continue;
+        }
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let day: u8 = name
+            .strip_prefix("day")
+            .and_then(|s| s.split('-').next())
+            .and_then(|d| d.parse().ok())
+            .unwrap_or_else(|| panic!("{name} doesn't start with day<N>-"));
+        let solution = SOLUTIONS.iter().find(|s| s.day == day).unwrap();
+        let input = Input {
+            day,
+            label: name.clone(),
+            path,
+        };
+        let data = input.read().unwrap();
+        let answers = input.read_answers().unwrap();
+
+        for part in 1..=solution.parts.len() as u8 {
+            for (variant, f) in solution.variants(part) {
+                let name = format!("{name} part {part} [{variant}]");
+                match outcome(f, &data) {
+                    Outcome::Panic(message) => panic!("{name} panics: {message}"),
+                    Outcome::Answer(answer) => {
+                        if let Some(expected) = answers.get(&part) {
+                            assert_eq!(&answer, expected, "{name}");
+                        }
+                    }
+                    Outcome::Error(_) => assert!(!answers.contains_key(&part), "{name}"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs;
+
+use anyhow::Result;
+use aoc::sim::{read_trace, replay, Driver, Simulation};
+use year2022::day14::sim::Pouring;
+use year2022::{day14, day9};
+
+fn example(day: u8) -> String {
+    fs::read_to_string(format!("test_data/day{day}.txt")).unwrap()
+}
+
+#[test]
+fn test_day9_rope() -> Result<()> {
+    let mut driver = Driver::new(day9::sim::load(&example(9), 1)?);
+    assert_eq!(driver.run_steps(4)?, 4);
+    assert_eq!(driver.simulation().render(), "s..TH\n");
+    assert_eq!(driver.run()?, 20);
+    assert_eq!(driver.simulation().state(), "8 0 2,2 1,2");
+    assert_eq!(driver.simulation().render(), ".TH\n...\ns..\n");
+
+    let mut rope = day9::sim::load(&example(9), 2)?;
+    rope.restore("7 1 1,2 1,1 1,1 0,0 0,0 0,0 0,0 0,0 0,0 0,0")?;
+    rope.step();
+    assert_eq!(rope.state(), "8 0 2,2 1,1 1,1 0,0 0,0 0,0 0,0 0,0 0,0 0,0");
+    assert!(rope.done());
+    assert!(rope.restore("7 1 1,2 1,1").is_err());
+    assert!(rope
+        .restore("9 0 0,0 0,0 0,0 0,0 0,0 0,0 0,0 0,0 0,0 0,0")
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_day14_pouring() -> Result<()> {
+    for (part, resting) in [(1, 24), (2, 93)] {
+        let (rocks, max_coords, min_coords) = day14::parse(&example(14))?;
+        let start = day14::pouring_start(&max_coords, &min_coords);
+        let grid = match part {
+            1 => day14::create_grid(&rocks, &max_coords, &min_coords),
+            _ => day14::create_grid_with_floor(&rocks, &max_coords, &min_coords),
+        };
+        let mut pouring = Pouring::new(grid, start);
+        while !pouring.done() {
+            pouring.step();
+        }
+        assert_eq!(pouring.resting(), resting, "part {part}");
+    }
+
+    let mut pouring = day14::sim::load(&example(14), 2)?;
+    pouring.step();
+    pouring.step();
+    assert_eq!(pouring.values(), [("resting", 0), ("x", 500), ("y", 1)]);
+    let (row, col) = pouring.highlighted()[0];
+    assert_eq!(
+        pouring.render().lines().nth(row).unwrap().chars().nth(col),
+        Some('~')
+    );
+    Ok(())
+}
+
+#[test]
+fn test_record_and_replay() -> Result<()> {
+    let mut trace = Vec::new();
+    let mut driver = Driver::new(day14::sim::load(&example(14), 1)?);
+    driver.record(&mut trace)?;
+    let steps = driver.run()?;
+    let last = driver.simulation().render();
+    assert!(last.contains('~'));
+    drop(driver);
+
+    let states = read_trace(trace.as_slice())?;
+    assert_eq!(states.len(), steps + 1);
+    assert_eq!(states[0], "0 -");
+    let mut pouring = day14::sim::load(&example(14), 1)?;
+    let frames = replay(pouring.as_mut(), &states)?;
+    assert_eq!(frames.last(), Some(&last));
+    let (rocks, max_coords, min_coords) = day14::parse(&example(14))?;
+    let grid = day14::create_grid(&rocks, &max_coords, &min_coords);
+    assert_eq!(frames[0], day14::render(&grid));
+
+    assert!(pouring.restore("25 -").is_err());
+    assert!(pouring.restore("3 0,0").is_ok());
+    let frame = pouring.render();
+    assert!(pouring.restore("3 99,0").is_err());
+    assert!(pouring.restore("25 -").is_err());
+    // States that can't be reached leave the simulation as it was
+    assert_eq!(pouring.state(), "3 0,0");
+    assert_eq!(pouring.render(), frame);
+
+    // Going back takes away the last units, going forward pours them again
+    let resting: Vec<&String> = states.iter().filter(|s| s.ends_with(" -")).collect();
+    for units in [20, 5, 0, 24, 12] {
+        let state = resting[units];
+        pouring.restore(state)?;
+        let step = states.iter().position(|s| s == state).unwrap();
+        assert_eq!(pouring.render(), frames[step], "{state}");
+    }
+    Ok(())
+}
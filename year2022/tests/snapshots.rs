@@ -0,0 +1,33 @@
+use std::fs;
+
+use anyhow::Result;
+use aoc::assert_snapshot;
+use year2022::{day10, day14};
+
+fn example(day: u8) -> String {
+    fs::read_to_string(format!("test_data/day{day}.txt")).unwrap()
+}
+
+#[test]
+fn test_day10_screen() -> Result<()> {
+    let x_reg_history = day10::run_program(&day10::parse(&example(10))?);
+    assert_snapshot!("day10-screen", day10::render(&x_reg_history));
+    Ok(())
+}
+
+#[test]
+fn test_day14_caves() -> Result<()> {
+    let (rocks, max_coords, min_coords) = day14::parse(&example(14))?;
+    let start = day14::pouring_start(&max_coords, &min_coords);
+
+    let mut grid = day14::create_grid(&rocks, &max_coords, &min_coords);
+    assert_snapshot!("day14-rocks", day14::render(&grid));
+    let nrows = grid.nrows();
+    day14::pour_sand(&mut grid, start, |sand| sand.0 != nrows - 1);
+    assert_snapshot!("day14-abyss", day14::render(&grid));
+
+    let mut grid = day14::create_grid_with_floor(&rocks, &max_coords, &min_coords);
+    day14::pour_sand(&mut grid, start, |sand| sand != start);
+    assert_snapshot!("day14-floor", day14::render(&grid));
+    Ok(())
+}